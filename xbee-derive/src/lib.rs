@@ -261,20 +261,20 @@ pub fn derive_inner_data(input: TokenStream) -> TokenStream {
                             #trait_ident::write(&self.#fields_iter, stream);
                         )*
                     }
-                    fn read<#unique_ty_ident: ReadStream>(stream: &mut #unique_ty_ident, max_size: usize) -> Self {
+                    fn read<#unique_ty_ident: ReadStream>(stream: &mut #unique_ty_ident, max_size: usize) -> Result<Self, DecodeError> {
                         let mut field_size = Self::MAX_SIZE
                                         .map_or(max_size, |c_max_size| c_max_size.min(max_size))
                                         .checked_sub(Self::MIN_SIZE)
-                                        .expect("Called `InnerData::read` with `max_size` that is less than the minimum `InnerData::MIN_SIZE`");
+                                        .ok_or(DecodeError::Truncated)?;
 
-                        Self {
+                        Ok(Self {
                             #(#fields_iter: {
                                 field_size += <#field_types as #trait_ident>::MIN_SIZE;
-                                let value = <#field_types as #trait_ident>::read(stream, field_size);
+                                let value = <#field_types as #trait_ident>::read(stream, field_size)?;
                                 field_size -= value.byte_size();
                                 value
                             },)*
-                        }
+                        })
                     }
                 }
             }
@@ -0,0 +1,222 @@
+use crate::at::{AtRequest, AtValue};
+use crate::driver::at_value;
+use crate::frames::{ApiMode, Frame, FrameData, FrameDecoder, ReceivedFrame, RemoteCommandOpts};
+use crate::stream::{Endianness, InnerData, SliceWriter, WriteStream};
+#[cfg(feature = "zigbee")]
+use crate::SourceRoutes;
+use crate::{Error, FrameIds, IeeeAddress, NetworkAddress, PendingRequest};
+use embedded_io_async::{Read, Write};
+
+/// An XBee on an async serial port, `N` bounds the size of a frame in either direction, `M`
+/// the number of requests that can wait on a response at once and `R` the number of source
+/// routes kept, which only matters with the `zigbee` feature.
+///
+/// Nothing here times out by itself, wrap the futures in your executor's timeout
+/// (e.g. `embassy_time::with_timeout`) where needed.
+#[derive(Debug)]
+pub struct Xbee<S, const N: usize, const M: usize = 8, const R: usize = 0> {
+    serial: S,
+    mode: ApiMode,
+    decoder: FrameDecoder<N>,
+    frame_ids: FrameIds<M>,
+    #[cfg(feature = "zigbee")]
+    source_routes: SourceRoutes<R>,
+    rx_buf: [u8; 32],
+    rx_start: usize,
+    rx_end: usize,
+    tx_buf: [u8; N],
+}
+
+impl<S, const N: usize, const M: usize, const R: usize> Xbee<S, N, M, R> {
+    pub fn new(serial: S, mode: ApiMode) -> Self {
+        Self {
+            serial,
+            mode,
+            decoder: FrameDecoder::new(mode),
+            frame_ids: FrameIds::default(),
+            #[cfg(feature = "zigbee")]
+            source_routes: SourceRoutes::new(),
+            rx_buf: [0; 32],
+            rx_start: 0,
+            rx_end: 0,
+            tx_buf: [0; N],
+        }
+    }
+
+    pub fn mode(&self) -> ApiMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: ApiMode) {
+        self.mode = mode;
+        self.decoder.set_mode(mode);
+    }
+
+    pub fn frame_ids(&mut self) -> &mut FrameIds<M> {
+        &mut self.frame_ids
+    }
+
+    #[cfg(feature = "zigbee")]
+    pub fn source_routes(&mut self) -> &mut SourceRoutes<R> {
+        &mut self.source_routes
+    }
+
+    pub fn serial(&mut self) -> &mut S {
+        &mut self.serial
+    }
+
+    pub fn release(self) -> S {
+        self.serial
+    }
+}
+
+impl<S: Write, const N: usize, const M: usize, const R: usize> Xbee<S, N, M, R> {
+    /// Sends `data`, preceded by a `CreateSourceRoute` if it's a unicast to a node with a
+    /// known source route.
+    pub async fn send<T: FrameData>(&mut self, data: T) -> Result<(), Error<S::Error>> {
+        #[cfg(feature = "zigbee")]
+        if let Some(route) = self.source_routes.ahead_of(&data) {
+            self.write_frame(route).await?;
+        }
+        self.write_frame(data).await
+    }
+
+    async fn write_frame<T: FrameData>(&mut self, data: T) -> Result<(), Error<S::Error>> {
+        let writer = &mut SliceWriter::new(&mut self.tx_buf[..], Endianness::BigEndian);
+        Frame(data).write_mode(self.mode, &mut |bytes: &[u8]| writer.write(bytes));
+        let len = writer.position();
+        if writer.overflowed() {
+            return Err(Error::FrameTooLarge);
+        }
+
+        self.serial
+            .write_all(&self.tx_buf[..len])
+            .await
+            .map_err(Error::Serial)?;
+        self.serial.flush().await.map_err(Error::Serial)
+    }
+
+    /// Sends the frame `make` builds around a freshly allocated frame ID, and returns the ID
+    /// to look for in the response. `now` is in the unit of the frame ID timeout.
+    pub async fn send_request<T: FrameData>(
+        &mut self,
+        now: u64,
+        make: impl FnOnce(u8) -> T,
+    ) -> Result<u8, Error<S::Error>> {
+        let id = self
+            .frame_ids
+            .allocate(T::API_TYPE, now)
+            .ok_or(Error::NoFreeFrameId)?;
+        if let Err(err) = self.send(make(id)).await {
+            self.frame_ids.cancel(id);
+            return Err(err);
+        }
+        Ok(id)
+    }
+}
+
+impl<S: Read, const N: usize, const M: usize, const R: usize> Xbee<S, N, M, R> {
+    /// Waits for the next frame. A response to a request from [`Xbee::send_request`] frees its
+    /// frame ID and comes with that request, and a route record updates the source routes.
+    pub async fn recv(
+        &mut self,
+    ) -> Result<(ReceivedFrame<N>, Option<PendingRequest>), Error<S::Error>> {
+        let frame = self.read_frame().await?;
+        let request = self.frame_ids.resolve(&frame);
+        Ok((frame, request))
+    }
+
+    async fn read_frame(&mut self) -> Result<ReceivedFrame<N>, Error<S::Error>> {
+        loop {
+            while self.rx_start < self.rx_end {
+                let byte = self.rx_buf[self.rx_start];
+                self.rx_start += 1;
+                if let Some(frame) = self.decoder.push(byte) {
+                    let frame = frame.try_into()?;
+                    #[cfg(feature = "zigbee")]
+                    self.source_routes.learn_from(&frame);
+                    return Ok(frame);
+                }
+            }
+
+            let len = self
+                .serial
+                .read(&mut self.rx_buf)
+                .await
+                .map_err(Error::Serial)?;
+            if len == 0 {
+                return Err(Error::UnexpectedEof);
+            }
+            self.rx_start = 0;
+            self.rx_end = len;
+        }
+    }
+}
+
+impl<S: Read + Write, const N: usize, const M: usize, const R: usize> Xbee<S, N, M, R> {
+    /// Sends the frame `make` builds around a fresh frame ID and waits for the response to it,
+    /// handing any other frame received meanwhile to `unmatched`, along with the request it
+    /// answers like [`Xbee::recv`].
+    ///
+    /// Frames that arrive intact but don't decode are skipped. On any other error the frame ID
+    /// is given up.
+    pub async fn request<T: FrameData>(
+        &mut self,
+        now: u64,
+        make: impl FnOnce(u8) -> T,
+        mut unmatched: impl FnMut((ReceivedFrame<N>, Option<PendingRequest>)),
+    ) -> Result<ReceivedFrame<N>, Error<S::Error>> {
+        let id = self.send_request(now, make).await?;
+        loop {
+            let frame = match self.read_frame().await {
+                Ok(frame) => frame,
+                Err(Error::Decode(_)) => continue,
+                Err(err) => {
+                    self.frame_ids.cancel(id);
+                    return Err(err);
+                }
+            };
+            match self.frame_ids.resolve(&frame) {
+                Some(req) if req.id == id => return Ok(frame),
+                request => unmatched((frame, request)),
+            }
+        }
+    }
+
+    /// Runs an AT command on the radio and decodes the value it responds with, handing any
+    /// other frame received meanwhile to `unmatched`.
+    ///
+    /// ```ignore
+    /// let pan_id = xbee.at(now, at::PanId::query(), |_| {}).await?;
+    /// xbee.at(now, at::PanId::set(pan_id + 1), |_| {}).await?;
+    /// ```
+    pub async fn at<T: InnerData, V: AtValue>(
+        &mut self,
+        now: u64,
+        command: AtRequest<T, V>,
+        unmatched: impl FnMut((ReceivedFrame<N>, Option<PendingRequest>)),
+    ) -> Result<V, Error<S::Error>> {
+        let make = |id| command.with_id(id);
+        match self.request(now, make, unmatched).await? {
+            ReceivedFrame::LocalAtCommandResponse(response) => at_value(&response),
+            _ => unreachable!("only a local AT command response answers a local AT command"),
+        }
+    }
+
+    /// Runs an AT command on a remote radio like [`Xbee::at`].
+    pub async fn remote_at<T: InnerData, V: AtValue>(
+        &mut self,
+        now: u64,
+        ieee_address: IeeeAddress,
+        network_address: NetworkAddress,
+        remote_command_opts: RemoteCommandOpts,
+        command: AtRequest<T, V>,
+        unmatched: impl FnMut((ReceivedFrame<N>, Option<PendingRequest>)),
+    ) -> Result<V, Error<S::Error>> {
+        let make = |id| command.remote(id, ieee_address, network_address, remote_command_opts);
+        match self.request(now, make, unmatched).await? {
+            ReceivedFrame::RemoteAtCommandResponse(response) => at_value(&response),
+            _ => unreachable!("only a remote AT command response answers a remote AT command"),
+        }
+    }
+}
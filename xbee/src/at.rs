@@ -0,0 +1,414 @@
+//! Typed AT commands, each knowing its mnemonic and how its parameter is encoded.
+//!
+//! ```ignore
+//! xbee.at(now, at::PanId::set(0x1234), |_| {})?;
+//! let pan_id: u64 = xbee.at(now, at::PanId::query(), |_| {})?;
+//! ```
+//!
+//! [`Xbee::at`](crate::Xbee::at) fails with [`Error::AtCommand`](crate::Error::AtCommand) when
+//! the radio rejects the command. Without the driver, check
+//! [`AtResponseStatus::result`] before decoding the value with e.g. `PanId::parse`.
+//!
+//! The same requests go to a remote radio with [`AtRequest::remote`].
+
+use crate::frames::{
+    AtResponseStatus, LocalAtCommandRequest, LocalAtCommandResponse, QueueLocalAtCommandRequest,
+    RemoteAtCommandRequest, RemoteAtCommandResponse, RemoteCommandOpts,
+};
+use crate::stream::{self, DecodeError, Endianness, InnerData, ReadStream, WriteStream};
+use crate::{inner_data_enum, ClusterId, Endpoint, IeeeAddress, NetworkAddress};
+use core::marker::PhantomData;
+
+pub trait AtCommand {
+    const COMMAND: [u8; 2];
+    /// The parameter the command is set with, or that a query responds with.
+    type Value: AtValue;
+}
+
+/// A value carried by an AT command, encoded big-endian without any length prefix.
+pub trait AtValue: InnerData {
+    /// Decodes the command data of a response.
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        Self::read(
+            &mut stream::SliceReader::new(data, Endianness::BigEndian),
+            data.len(),
+        )
+    }
+}
+
+// The radio may drop leading zero bytes of a number, so anything up to the full width decodes.
+// Only zero bytes are dropped, so a shortened signed number isn't sign extended.
+macro_rules! at_value_int {
+    ($($int:ident),*) => {$(
+        impl AtValue for $int {
+            fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+                if data.is_empty() {
+                    return Err(DecodeError::Truncated);
+                }
+                if Self::MIN_SIZE < data.len() {
+                    return Err(DecodeError::CapacityOverflow);
+                }
+                let mut bytes = [0; Self::MIN_SIZE];
+                bytes[Self::MIN_SIZE - data.len()..].copy_from_slice(data);
+                Ok(Self::from_be_bytes(bytes))
+            }
+        }
+    )*};
+}
+
+at_value_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl AtValue for () {}
+
+impl AtValue for bool {
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        u8::decode(data).map(|x| x != 0)
+    }
+}
+
+impl AtValue for NetworkAddress {
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        u16::decode(data).map(Self)
+    }
+}
+
+impl AtValue for Endpoint {
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        u8::decode(data).map(Self)
+    }
+}
+
+impl AtValue for ClusterId {
+    fn decode(data: &[u8]) -> Result<Self, DecodeError> {
+        u16::decode(data).map(Self)
+    }
+}
+
+impl<const N: usize> AtValue for stream::HungryString<N> {}
+
+impl<const N: usize> AtValue for stream::HungryVec<u8, N> {}
+
+/// An AT command with its parameter, waiting for a frame ID. `V` is the value the radio
+/// responds with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtRequest<T, V = ()> {
+    pub at_command: [u8; 2],
+    pub parameter: T,
+    _response: PhantomData<fn() -> V>,
+}
+
+impl<T: InnerData, V: AtValue> AtRequest<T, V> {
+    pub const fn new(at_command: [u8; 2], parameter: T) -> Self {
+        Self {
+            at_command,
+            parameter,
+            _response: PhantomData,
+        }
+    }
+
+    /// The request to run the command immediately, ID 0 means no response.
+    pub fn with_id(self, id: u8) -> LocalAtCommandRequest<T> {
+        LocalAtCommandRequest {
+            id,
+            at_command: self.at_command,
+            parameter: self.parameter,
+        }
+    }
+
+    /// The request to queue the command until `AC` applies the changes.
+    pub fn queued(self, id: u8) -> QueueLocalAtCommandRequest<T> {
+        QueueLocalAtCommandRequest {
+            id,
+            at_command: self.at_command,
+            parameter: self.parameter,
+        }
+    }
+
+    /// The request to run the command on the radio at `ieee_address`, or at `network_address`
+    /// when `ieee_address` is [`IeeeAddress::UNKNOWN`].
+    pub fn remote(
+        self,
+        id: u8,
+        ieee_address: IeeeAddress,
+        network_address: NetworkAddress,
+        remote_command_opts: RemoteCommandOpts,
+    ) -> RemoteAtCommandRequest<T> {
+        RemoteAtCommandRequest {
+            id,
+            ieee_address,
+            network_address,
+            remote_command_opts,
+            at_command: self.at_command,
+            parameter: self.parameter,
+        }
+    }
+}
+
+/// A response to a local or remote AT command.
+pub trait AtResponse {
+    fn at_command(&self) -> [u8; 2];
+    fn command_status(&self) -> AtResponseStatus;
+    fn command_data(&self) -> &[u8];
+}
+
+impl<const N: usize> AtResponse for LocalAtCommandResponse<stream::HungryVec<u8, N>> {
+    fn at_command(&self) -> [u8; 2] {
+        self.at_command
+    }
+    fn command_status(&self) -> AtResponseStatus {
+        self.command_status
+    }
+    fn command_data(&self) -> &[u8] {
+        &self.command_data
+    }
+}
+
+impl<const N: usize> AtResponse for RemoteAtCommandResponse<stream::HungryVec<u8, N>> {
+    fn at_command(&self) -> [u8; 2] {
+        self.at_command
+    }
+    fn command_status(&self) -> AtResponseStatus {
+        self.command_status
+    }
+    fn command_data(&self) -> &[u8] {
+        &self.command_data
+    }
+}
+
+macro_rules! at_commands {
+    () => {};
+    (
+        $(#[$attr:meta])*
+        $name:ident = $command:literal: $ty:ty [$($cap:ident),+];
+        $($tail:tt)*
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl AtCommand for $name {
+            const COMMAND: [u8; 2] = *$command;
+            type Value = $ty;
+        }
+
+        impl $name {
+            $(at_commands!(@$cap $ty);)+
+        }
+
+        at_commands!($($tail)*);
+    };
+    (@query $ty:ty) => {
+        pub const fn query() -> AtRequest<(), $ty> {
+            AtRequest::new(<Self as AtCommand>::COMMAND, ())
+        }
+
+        /// Decodes the value in a response, whatever its status. [`Xbee::at`](crate::Xbee::at)
+        /// checks the status first.
+        pub fn parse(response: &impl AtResponse) -> Result<$ty, DecodeError> {
+            <$ty as AtValue>::decode(response.command_data())
+        }
+    };
+    (@set $ty:ty) => {
+        pub const fn set(value: $ty) -> AtRequest<$ty> {
+            AtRequest::new(<Self as AtCommand>::COMMAND, value)
+        }
+    };
+    (@execute $ty:ty) => {
+        pub const fn execute() -> AtRequest<()> {
+            AtRequest::new(<Self as AtCommand>::COMMAND, ())
+        }
+    };
+}
+
+inner_data_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ApiSetting: u8 {
+        Transparent = 0,
+        Api = 1,
+        ApiEscaped = 2,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum BaudRate: u8 {
+        Baud1200 = 0,
+        Baud2400 = 1,
+        Baud4800 = 2,
+        Baud9600 = 3,
+        Baud19200 = 4,
+        Baud38400 = 5,
+        Baud57600 = 6,
+        Baud115200 = 7,
+        Baud230400 = 8,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Parity: u8 {
+        None = 0,
+        Even = 1,
+        Odd = 2,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StopBits: u8 {
+        One = 0,
+        Two = 1,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum SleepType: u8 {
+        NoSleep = 0,
+        PinSleep = 1,
+        CyclicSleep = 4,
+        CyclicSleepPinWake = 5,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum PinMode: u8 {
+        Disabled = 0,
+        /// The alternate function of the pin, e.g. the commissioning button on `D0` or the
+        /// associate LED on `D5`.
+        Alternate = 1,
+        AnalogInput = 2,
+        DigitalInput = 3,
+        DigitalOutputLow = 4,
+        DigitalOutputHigh = 5,
+    }
+}
+
+impl AtValue for ApiSetting {}
+impl AtValue for BaudRate {}
+impl AtValue for Parity {}
+impl AtValue for StopBits {}
+impl AtValue for SleepType {}
+impl AtValue for PinMode {}
+
+/// The longest node identifier the radio stores.
+pub const NODE_IDENTIFIER_LEN: usize = 20;
+
+// Networking
+at_commands! {
+    /// Extended PAN ID to join or form, 0 lets the coordinator pick one.
+    PanId = b"ID": u64 [query, set];
+    OperatingPanId = b"OP": u64 [query];
+    OperatingPanId16 = b"OI": u16 [query];
+    OperatingChannel = b"CH": u8 [query];
+    /// Bitmask of the channels to scan, bit 0 being channel 11.
+    ScanChannels = b"SC": u16 [query, set];
+    ScanDuration = b"SD": u8 [query, set];
+    ZigbeeStackProfile = b"ZS": u8 [query, set];
+    /// Seconds the node allows joining after forming or joining, 0xff allows it forever.
+    NodeJoinTime = b"NJ": u8 [query, set];
+    CoordinatorEnable = b"CE": bool [query, set];
+    ChannelVerification = b"JV": bool [query, set];
+    JoinNotification = b"JN": bool [query, set];
+    NetworkWatchdogTimeout = b"NW": u16 [query, set];
+    AssociationIndication = b"AI": u8 [query];
+    NumberOfRemainingChildren = b"NC": u8 [query];
+    /// 0 resets the network layer of this node, 1 of the whole network.
+    NetworkReset = b"NR": u8 [set];
+    ForceDisassociation = b"DA": () [execute];
+}
+
+// Security
+at_commands! {
+    EncryptionEnable = b"EE": bool [query, set];
+    EncryptionOptions = b"EO": u8 [query, set];
+    /// Write only, the radio never reports its keys.
+    LinkKey = b"KY": u128 [set];
+    /// Write only, 0 lets the coordinator pick a random key.
+    NetworkKey = b"NK": u128 [set];
+}
+
+// Addressing
+at_commands! {
+    SerialNumberHigh = b"SH": u32 [query];
+    SerialNumberLow = b"SL": u32 [query];
+    NetworkAddress16 = b"MY": NetworkAddress [query];
+    ParentAddress = b"MP": NetworkAddress [query];
+    DestinationHigh = b"DH": u32 [query, set];
+    DestinationLow = b"DL": u32 [query, set];
+    NodeIdentifier = b"NI": stream::HungryString<NODE_IDENTIFIER_LEN> [query, set];
+    MaximumHops = b"NH": u8 [query, set];
+    BroadcastRadius = b"BH": u8 [query, set];
+    NodeDiscoveryTimeout = b"NT": u8 [query, set];
+    NodeDiscoveryOptions = b"NO": u8 [query, set];
+    SourceEndpoint = b"SE": Endpoint [query, set];
+    DestinationEndpoint = b"DE": Endpoint [query, set];
+    ClusterIdentifier = b"CI": ClusterId [query, set];
+    TransmitOptions = b"TO": u8 [query, set];
+}
+
+// Serial interfacing
+at_commands! {
+    InterfaceDataRate = b"BD": BaudRate [query, set];
+    SerialParity = b"NB": Parity [query, set];
+    SerialStopBits = b"SB": StopBits [query, set];
+    PacketizationTimeout = b"RO": u8 [query, set];
+    ApiEnable = b"AP": ApiSetting [query, set];
+    ApiOptions = b"AO": u8 [query, set];
+}
+
+// Sleep
+at_commands! {
+    SleepMode = b"SM": SleepType [query, set];
+    NumberOfSleepPeriods = b"SN": u16 [query, set];
+    /// In units of 10 ms.
+    SleepPeriod = b"SP": u16 [query, set];
+    /// In ms.
+    TimeBeforeSleep = b"ST": u16 [query, set];
+    SleepOptions = b"SO": u8 [query, set];
+    /// In ms.
+    WakeHost = b"WH": u16 [query, set];
+    /// In units of 10 ms.
+    PollingRate = b"PO": u16 [query, set];
+}
+
+// I/O
+at_commands! {
+    Dio0 = b"D0": PinMode [query, set];
+    Dio1 = b"D1": PinMode [query, set];
+    Dio2 = b"D2": PinMode [query, set];
+    Dio3 = b"D3": PinMode [query, set];
+    Dio4 = b"D4": PinMode [query, set];
+    Dio5 = b"D5": PinMode [query, set];
+    Dio6 = b"D6": PinMode [query, set];
+    Dio7 = b"D7": PinMode [query, set];
+    Dio10 = b"P0": PinMode [query, set];
+    Dio11 = b"P1": PinMode [query, set];
+    Dio12 = b"P2": PinMode [query, set];
+    /// In ms, 0 disables periodic sampling.
+    IoSampleRate = b"IR": u16 [query, set];
+    /// Bitmask of the digital pins that send a sample when they change.
+    DigitalChangeDetection = b"IC": u16 [query, set];
+    PullUpResistors = b"PR": u16 [query, set];
+    PullUpDirection = b"PD": u16 [query, set];
+    /// In units of 10 ms, 0 picks the default.
+    AssociateLedBlinkTime = b"LT": u8 [query, set];
+    /// In units of 100 ms.
+    RssiPwmTimer = b"RP": u8 [query, set];
+    /// In mV, the supply voltage under which IO samples include the supply voltage.
+    SupplyVoltageThreshold = b"V+": u16 [query, set];
+}
+
+// Diagnostics
+at_commands! {
+    FirmwareVersion = b"VR": u16 [query];
+    HardwareVersion = b"HV": u16 [query];
+    DeviceType = b"DD": u32 [query, set];
+    /// -dBm of the last received packet.
+    ReceivedSignalStrength = b"DB": u8 [query];
+    /// In °C, two's complement.
+    Temperature = b"TP": i16 [query];
+    /// In mV.
+    SupplyVoltage = b"%V": u16 [query];
+    MaximumPayload = b"NP": u16 [query];
+    ConfigurationChecksum = b"CK": u16 [query];
+}
+
+// Commands
+at_commands! {
+    ApplyChanges = b"AC": () [execute];
+    Write = b"WR": () [execute];
+    RestoreDefaults = b"RE": () [execute];
+    SoftwareReset = b"FR": () [execute];
+}
@@ -0,0 +1,574 @@
+use crate::at::{AtRequest, AtResponse, AtValue};
+use crate::frames::{
+    ApiMode, AtCommandStatus, Frame, FrameData, FrameDecoder, ReceivedFrame, RemoteCommandOpts,
+};
+#[cfg(feature = "zigbee")]
+use crate::frames::{CreateSourceRoute, RouteRecordIndicator, MAX_SOURCE_ROUTE_HOPS};
+use crate::{DecodeError, IeeeAddress, InnerData, NetworkAddress};
+use core::slice;
+use embedded_io::{Read, ReadReady, Write};
+#[cfg(feature = "zigbee")]
+use heapless::Vec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Error<E> {
+    Serial(E),
+    /// A frame arrived intact but its data didn't decode.
+    Decode(DecodeError),
+    /// The encoded frame doesn't fit in the driver's transmit buffer.
+    FrameTooLarge,
+    /// The serial port reported end of file.
+    UnexpectedEof,
+    /// Every frame ID is taken by a request still waiting on its response.
+    NoFreeFrameId,
+    /// The radio answered an AT command with a status other than OK.
+    AtCommand(AtCommandStatus),
+}
+
+impl<E> From<DecodeError> for Error<E> {
+    fn from(err: DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PendingRequest {
+    pub id: u8,
+    pub api_type: u8,
+    pub deadline: u64,
+}
+
+/// Hands out non-zero frame IDs and tracks up to `M` requests waiting on their response.
+///
+/// Times are in whatever monotonic unit the caller passes as `now`, the drivers default to
+/// milliseconds. An ID isn't handed out again while its request is pending, and IDs cycle
+/// through all 255 values so a late response to an expired request is unlikely to be taken
+/// for the answer to a newer one.
+#[derive(Debug, Clone)]
+pub struct FrameIds<const M: usize> {
+    last_id: u8,
+    timeout: u64,
+    pending: [Option<PendingRequest>; M],
+}
+
+impl<const M: usize> FrameIds<M> {
+    pub const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+    pub const fn new(timeout: u64) -> Self {
+        Self {
+            last_id: 0,
+            timeout,
+            pending: [None; M],
+        }
+    }
+
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: u64) {
+        self.timeout = timeout;
+    }
+
+    /// Allocates an ID for a request of type `api_type` sent at `now`, after expiring the
+    /// requests whose deadline passed.
+    pub fn allocate(&mut self, api_type: u8, now: u64) -> Option<u8> {
+        self.expire(now, |_| {});
+        let slot = self.pending.iter().position(Option::is_none)?;
+        let id = (1..=u8::MAX)
+            .map(|i| self.last_id.wrapping_add(i))
+            .find(|&id| id != 0 && !self.is_pending(id))?;
+        self.last_id = id;
+        self.pending[slot] = Some(PendingRequest {
+            id,
+            api_type,
+            deadline: now.saturating_add(self.timeout),
+        });
+        Some(id)
+    }
+
+    pub fn is_pending(&self, id: u8) -> bool {
+        self.pending.iter().flatten().any(|req| req.id == id)
+    }
+
+    pub fn cancel(&mut self, id: u8) -> Option<PendingRequest> {
+        self.pending
+            .iter_mut()
+            .find(|req| matches!(req, Some(req) if req.id == id))?
+            .take()
+    }
+
+    /// Removes and returns the pending request that `frame` is the response to.
+    pub fn resolve<const N: usize>(&mut self, frame: &ReceivedFrame<N>) -> Option<PendingRequest> {
+        let id = frame.frame_id()?;
+        let slot = self.pending.iter_mut().find(|req| {
+            matches!(req, Some(req) if req.id == id && answers(req.api_type, frame.api_type()))
+        })?;
+        slot.take()
+    }
+
+    /// Removes the requests whose deadline passed at `now`, passing each to `expired`.
+    pub fn expire(&mut self, now: u64, mut expired: impl FnMut(PendingRequest)) {
+        for slot in &mut self.pending {
+            if let Some(req) = slot.filter(|req| req.deadline <= now) {
+                *slot = None;
+                expired(req);
+            }
+        }
+    }
+}
+
+impl<const M: usize> Default for FrameIds<M> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TIMEOUT_MS)
+    }
+}
+
+/// Whether a frame of type `response` can be the response to one of type `request`.
+fn answers(request: u8, response: u8) -> bool {
+    match response {
+        // Local AT command response to a local or queued AT command request.
+        0x88 => matches!(request, 0x08 | 0x09),
+        // Transmit status from radios that don't send the extended one, including to the
+        // 802.15.4 transmit requests with 64 and 16 bit addresses.
+        0x89 => matches!(request, 0x00 | 0x01 | 0x10 | 0x11),
+        // Extended transmit status to a transmit or explicit addressing request.
+        0x8b => matches!(request, 0x10 | 0x11),
+        // Remote AT command response to a remote AT command request.
+        0x97 => request == 0x17,
+        // Register device status to a register joining device request.
+        0xa4 => request == 0x24,
+        _ => false,
+    }
+}
+
+#[cfg(feature = "zigbee")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRoute {
+    pub ieee_address: IeeeAddress,
+    pub network_address: NetworkAddress,
+    /// The intermediate hops, starting with the neighbor of the destination.
+    pub hops: Vec<NetworkAddress, MAX_SOURCE_ROUTE_HOPS>,
+}
+
+#[cfg(feature = "zigbee")]
+/// Source routes learned from [`RouteRecordIndicator`]s, keeping the `R` most recently
+/// learned ones.
+///
+/// The drivers send a [`CreateSourceRoute`] ahead of every unicast to a node with a route
+/// through intermediate hops, so the radio doesn't have to discover a route itself.
+#[derive(Debug, Clone)]
+pub struct SourceRoutes<const R: usize> {
+    routes: Vec<SourceRoute, R>,
+}
+
+#[cfg(feature = "zigbee")]
+impl<const R: usize> SourceRoutes<R> {
+    pub const fn new() -> Self {
+        Self { routes: Vec::new() }
+    }
+
+    pub fn learn(&mut self, record: &RouteRecordIndicator) {
+        self.forget(record.source_ieee_address, record.source_network_address);
+        if record.addresses.is_empty() || R == 0 {
+            return;
+        }
+        if self.routes.is_full() {
+            self.routes.remove(0);
+        }
+        self.routes
+            .push(SourceRoute {
+                ieee_address: record.source_ieee_address,
+                network_address: record.source_network_address,
+                // Already listed from the source's neighbor, which is the destination's neighbor
+                // of the route back.
+                hops: record.addresses.clone().into_inner(),
+            })
+            .ok();
+    }
+
+    /// The route to the node at `ieee_address`, or at `network_address` when `ieee_address`
+    /// is [`IeeeAddress::UNKNOWN`].
+    pub fn get(
+        &self,
+        ieee_address: IeeeAddress,
+        network_address: NetworkAddress,
+    ) -> Option<&SourceRoute> {
+        self.routes
+            .iter()
+            .find(|route| route.matches(ieee_address, network_address))
+    }
+
+    pub fn forget(
+        &mut self,
+        ieee_address: IeeeAddress,
+        network_address: NetworkAddress,
+    ) -> Option<SourceRoute> {
+        let index = self
+            .routes
+            .iter()
+            .position(|route| route.matches(ieee_address, network_address))?;
+        Some(self.routes.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        self.routes.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SourceRoute> {
+        self.routes.iter()
+    }
+
+    /// The frame that installs the route to a node, if it has one.
+    pub fn create_source_route(
+        &self,
+        ieee_address: IeeeAddress,
+        network_address: NetworkAddress,
+    ) -> Option<CreateSourceRoute> {
+        let route = self.get(ieee_address, network_address)?;
+        Some(CreateSourceRoute {
+            id: 0,
+            ieee_address: route.ieee_address,
+            network_address: route.network_address,
+            route_command_opts: 0,
+            addresses: route.hops.clone().into(),
+        })
+    }
+
+    pub(crate) fn learn_from<const N: usize>(&mut self, frame: &ReceivedFrame<N>) {
+        if let ReceivedFrame::RouteRecordIndicator(record) = frame {
+            self.learn(record);
+        }
+    }
+
+    /// The frame to send ahead of `data`, if any.
+    pub(crate) fn ahead_of<T: FrameData>(&self, data: &T) -> Option<CreateSourceRoute> {
+        let (ieee_address, network_address) = data.unicast_destination()?;
+        self.create_source_route(ieee_address, network_address)
+    }
+}
+
+#[cfg(feature = "zigbee")]
+impl<const R: usize> Default for SourceRoutes<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zigbee")]
+impl SourceRoute {
+    fn matches(&self, ieee_address: IeeeAddress, network_address: NetworkAddress) -> bool {
+        if ieee_address == IeeeAddress::UNKNOWN {
+            self.network_address == network_address
+        } else {
+            self.ieee_address == ieee_address
+        }
+    }
+}
+
+/// An XBee on a serial port, `N` bounds the size of a received frame, `M` the number of
+/// requests that can wait on a response at once and `R` the number of source routes kept, which
+/// only matters with the `zigbee` feature.
+#[derive(Debug)]
+pub struct Xbee<S, const N: usize, const M: usize = 8, const R: usize = 0> {
+    serial: S,
+    mode: ApiMode,
+    decoder: FrameDecoder<N>,
+    frame_ids: FrameIds<M>,
+    #[cfg(feature = "zigbee")]
+    source_routes: SourceRoutes<R>,
+}
+
+impl<S, const N: usize, const M: usize, const R: usize> Xbee<S, N, M, R> {
+    pub fn new(serial: S, mode: ApiMode) -> Self {
+        Self {
+            serial,
+            mode,
+            decoder: FrameDecoder::new(mode),
+            frame_ids: FrameIds::default(),
+            #[cfg(feature = "zigbee")]
+            source_routes: SourceRoutes::new(),
+        }
+    }
+
+    pub fn frame_ids(&mut self) -> &mut FrameIds<M> {
+        &mut self.frame_ids
+    }
+
+    #[cfg(feature = "zigbee")]
+    pub fn source_routes(&mut self) -> &mut SourceRoutes<R> {
+        &mut self.source_routes
+    }
+
+    pub fn mode(&self) -> ApiMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: ApiMode) {
+        self.mode = mode;
+        self.decoder.set_mode(mode);
+    }
+
+    pub fn serial(&mut self) -> &mut S {
+        &mut self.serial
+    }
+
+    pub fn release(self) -> S {
+        self.serial
+    }
+}
+
+impl<S: Write, const N: usize, const M: usize, const R: usize> Xbee<S, N, M, R> {
+    /// Sends `data`, preceded by a `CreateSourceRoute` if it's a unicast to a node with a
+    /// known source route.
+    pub fn send<T: FrameData>(&mut self, data: T) -> Result<(), Error<S::Error>> {
+        #[cfg(feature = "zigbee")]
+        if let Some(route) = self.source_routes.ahead_of(&data) {
+            self.write_frame(route)?;
+        }
+        self.write_frame(data)
+    }
+
+    fn write_frame<T: FrameData>(&mut self, data: T) -> Result<(), Error<S::Error>> {
+        let serial = &mut self.serial;
+        let mut result = Ok(());
+        Frame(data).write_mode(self.mode, &mut |bytes: &[u8]| {
+            if result.is_ok() {
+                result = serial.write_all(bytes);
+            }
+        });
+        result.and_then(|()| serial.flush()).map_err(Error::Serial)
+    }
+
+    /// Sends the frame `make` builds around a freshly allocated frame ID, and returns the ID
+    /// to look for in the response. `now` is in the unit of the frame ID timeout.
+    pub fn send_request<T: FrameData>(
+        &mut self,
+        now: u64,
+        make: impl FnOnce(u8) -> T,
+    ) -> Result<u8, Error<S::Error>> {
+        let id = self
+            .frame_ids
+            .allocate(T::API_TYPE, now)
+            .ok_or(Error::NoFreeFrameId)?;
+        if let Err(err) = self.send(make(id)) {
+            self.frame_ids.cancel(id);
+            return Err(err);
+        }
+        Ok(id)
+    }
+}
+
+impl<S: Read + ReadReady, const N: usize, const M: usize, const R: usize> Xbee<S, N, M, R> {
+    /// Reads whatever the serial port has ready without blocking, returning the first frame
+    /// it completes. Bytes after that frame stay in the port for the next call.
+    ///
+    /// A response to a request from [`Xbee::send_request`] frees its frame ID and comes with
+    /// that request, and a route record updates the source routes.
+    #[allow(clippy::type_complexity)]
+    pub fn poll(
+        &mut self,
+    ) -> Result<Option<(ReceivedFrame<N>, Option<PendingRequest>)>, Error<S::Error>> {
+        while self.serial.read_ready().map_err(Error::Serial)? {
+            let mut byte = 0;
+            if self
+                .serial
+                .read(slice::from_mut(&mut byte))
+                .map_err(Error::Serial)?
+                == 0
+            {
+                break;
+            }
+            if let Some(frame) = self.decoder.push(byte) {
+                let frame = frame.try_into()?;
+                let request = self.frame_ids.resolve(&frame);
+                #[cfg(feature = "zigbee")]
+                self.source_routes.learn_from(&frame);
+                return Ok(Some((frame, request)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<S: Read + Write, const N: usize, const M: usize, const R: usize> Xbee<S, N, M, R> {
+    /// Sends the frame `make` builds around a fresh frame ID and blocks until the response to
+    /// it arrives, handing any other frame received meanwhile to `unmatched`, along with the
+    /// request it answers like [`Xbee::poll`].
+    ///
+    /// Frames that arrive intact but don't decode are skipped. On any other error the frame ID
+    /// is given up.
+    pub fn request<T: FrameData>(
+        &mut self,
+        now: u64,
+        make: impl FnOnce(u8) -> T,
+        mut unmatched: impl FnMut((ReceivedFrame<N>, Option<PendingRequest>)),
+    ) -> Result<ReceivedFrame<N>, Error<S::Error>> {
+        let id = self.send_request(now, make)?;
+        loop {
+            let frame = match self.read_frame() {
+                Ok(frame) => frame,
+                Err(Error::Decode(_)) => continue,
+                Err(err) => {
+                    self.frame_ids.cancel(id);
+                    return Err(err);
+                }
+            };
+            match self.frame_ids.resolve(&frame) {
+                Some(req) if req.id == id => return Ok(frame),
+                request => unmatched((frame, request)),
+            }
+        }
+    }
+
+    /// Runs an AT command on the radio and decodes the value it responds with, handing any
+    /// other frame received meanwhile to `unmatched`.
+    ///
+    /// ```ignore
+    /// let pan_id = xbee.at(now, at::PanId::query(), |_| {})?;
+    /// xbee.at(now, at::PanId::set(pan_id + 1), |_| {})?;
+    /// ```
+    pub fn at<T: InnerData, V: AtValue>(
+        &mut self,
+        now: u64,
+        command: AtRequest<T, V>,
+        unmatched: impl FnMut((ReceivedFrame<N>, Option<PendingRequest>)),
+    ) -> Result<V, Error<S::Error>> {
+        let make = |id| command.with_id(id);
+        match self.request(now, make, unmatched)? {
+            ReceivedFrame::LocalAtCommandResponse(response) => at_value(&response),
+            _ => unreachable!("only a local AT command response answers a local AT command"),
+        }
+    }
+
+    /// Runs an AT command on a remote radio like [`Xbee::at`].
+    pub fn remote_at<T: InnerData, V: AtValue>(
+        &mut self,
+        now: u64,
+        ieee_address: IeeeAddress,
+        network_address: NetworkAddress,
+        remote_command_opts: RemoteCommandOpts,
+        command: AtRequest<T, V>,
+        unmatched: impl FnMut((ReceivedFrame<N>, Option<PendingRequest>)),
+    ) -> Result<V, Error<S::Error>> {
+        let make = |id| command.remote(id, ieee_address, network_address, remote_command_opts);
+        match self.request(now, make, unmatched)? {
+            ReceivedFrame::RemoteAtCommandResponse(response) => at_value(&response),
+            _ => unreachable!("only a remote AT command response answers a remote AT command"),
+        }
+    }
+
+    fn read_frame(&mut self) -> Result<ReceivedFrame<N>, Error<S::Error>> {
+        loop {
+            let mut byte = 0;
+            if self
+                .serial
+                .read(slice::from_mut(&mut byte))
+                .map_err(Error::Serial)?
+                == 0
+            {
+                return Err(Error::UnexpectedEof);
+            }
+            if let Some(frame) = self.decoder.push(byte) {
+                let frame = frame.try_into()?;
+                #[cfg(feature = "zigbee")]
+                self.source_routes.learn_from(&frame);
+                return Ok(frame);
+            }
+        }
+    }
+}
+
+/// Checks the status of an AT command response and decodes its value.
+pub(crate) fn at_value<V: AtValue, E>(response: &impl AtResponse) -> Result<V, Error<E>> {
+    response
+        .command_status()
+        .result()
+        .map_err(Error::AtCommand)?;
+    Ok(V::decode(response.command_data())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    #[cfg(feature = "zigbee")]
+    #[test]
+    fn source_route_from_route_record() {
+        // The route record and create source route examples from the XBee Zigbee manual, for a
+        // route 0x3344 <-> 0xEEFF <-> 0xCCDD <-> 0xAABB <-> this node.
+        let record = [
+            0x7e, 0x00, 0x13, 0xa1, 0x00, 0x13, 0xa2, 0x00, 0x40, 0x40, 0x11, 0x22, 0x33, 0x44,
+            0x01, 0x03, 0xee, 0xff, 0xcc, 0xdd, 0xaa, 0xbb, 0x80,
+        ];
+        let expected = [
+            0x7e, 0x00, 0x14, 0x21, 0x00, 0x00, 0x13, 0xa2, 0x00, 0x40, 0x40, 0x11, 0x22, 0x33,
+            0x44, 0x00, 0x03, 0xee, 0xff, 0xcc, 0xdd, 0xaa, 0xbb, 0x01,
+        ];
+
+        let mut decoder = FrameDecoder::<64>::new(ApiMode::Unescaped);
+        let mut routes = SourceRoutes::<4>::new();
+        for byte in record {
+            if let Some(frame) = decoder.push(byte) {
+                let frame: ReceivedFrame<64> = frame.try_into().unwrap();
+                routes.learn_from(&frame);
+            }
+        }
+
+        let ieee_address = IeeeAddress(0x0013a200_40401122);
+        let network_address = NetworkAddress(0x3344);
+        let route = routes
+            .create_source_route(ieee_address, network_address)
+            .unwrap();
+        let mut bytes: Vec<u8, 64> = Vec::new();
+        Frame(route).write(&mut |b: &[u8]| bytes.extend_from_slice(b).unwrap());
+        assert_eq!(bytes[..], expected);
+    }
+
+    /// Replays `input` to the driver and keeps what it writes.
+    struct Serial<'a> {
+        input: &'a [u8],
+        output: Vec<u8, 64>,
+    }
+
+    impl embedded_io::ErrorType for Serial<'_> {
+        type Error = core::convert::Infallible;
+    }
+
+    impl Read for Serial<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let len = buf.len().min(self.input.len());
+            buf[..len].copy_from_slice(&self.input[..len]);
+            self.input = &self.input[len..];
+            Ok(len)
+        }
+    }
+
+    impl Write for Serial<'_> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.output.extend_from_slice(buf).unwrap();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn at_command_status_error() {
+        // The response to `ID` with frame ID 1, INVALID_COMMAND and no command data.
+        let input = [0x7e, 0x00, 0x05, 0x88, 0x01, b'I', b'D', 0x02, 0xe7];
+        let serial = Serial {
+            input: &input,
+            output: Vec::new(),
+        };
+        let mut xbee = Xbee::<_, 64>::new(serial, ApiMode::Unescaped);
+        assert_eq!(
+            xbee.at(0, crate::at::PanId::query(), |_| {}),
+            Err(Error::AtCommand(AtCommandStatus::INVALID_COMMAND))
+        );
+        assert!(!xbee.frame_ids().is_pending(1));
+    }
+}
@@ -1,9 +1,37 @@
-use crate::stream::{self, Endianness, InnerData, ReadStream, WriteStream};
-use crate::{ClusterId, Endpoint, IeeeAddress, NetworkAddress, ProfileId};
+use crate::stream::{self, DecodeError, Endianness, InnerData, ReadStream, WriteStream};
+use crate::{Cluster, ClusterId, Endpoint, IeeeAddress, NetworkAddress, ProfileId};
 use bitflags::bitflags;
 
 pub trait FrameData: InnerData {
     const API_TYPE: u8;
+
+    /// The frame ID that the response to this frame (or the request of this response) carries,
+    /// `None` for frames without one or with ID 0, which tells the radio not to respond.
+    fn frame_id(&self) -> Option<u8> {
+        None
+    }
+
+    /// The addresses of the single node a transmit request goes to, `None` for broadcasts and
+    /// frames that aren't sent anywhere.
+    fn unicast_destination(&self) -> Option<(IeeeAddress, NetworkAddress)> {
+        None
+    }
+}
+
+#[inline]
+fn unicast(
+    ieee_address: IeeeAddress,
+    network_address: NetworkAddress,
+    transmit_opts: TransmitOpts,
+) -> Option<(IeeeAddress, NetworkAddress)> {
+    let broadcast =
+        ieee_address == IeeeAddress::BROADCAST || transmit_opts.contains(TransmitOpts::MULTICAST);
+    (!broadcast).then_some((ieee_address, network_address))
+}
+
+#[inline]
+fn nonzero_frame_id(id: u8) -> Option<u8> {
+    (id != 0).then_some(id)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
@@ -16,6 +44,92 @@ pub struct LocalAtCommandRequest<T> {
 
 impl<T: InnerData> FrameData for LocalAtCommandRequest<T> {
     const API_TYPE: u8 = 0x08;
+
+    fn frame_id(&self) -> Option<u8> {
+        nonzero_frame_id(self.id)
+    }
+}
+
+/// The status of an AT command response, values not listed here are passed through as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+#[repr(transparent)]
+pub struct AtCommandStatus(pub u8);
+
+impl AtCommandStatus {
+    pub const OK: Self = Self(0x00);
+    pub const ERROR: Self = Self(0x01);
+    pub const INVALID_COMMAND: Self = Self(0x02);
+    pub const INVALID_PARAMETER: Self = Self(0x03);
+    /// Only for remote commands, the request never reached the remote radio.
+    pub const TX_FAILURE: Self = Self(0x04);
+    /// Only for remote commands, there's no secure session with the remote radio.
+    pub const NO_SECURE_SESSION: Self = Self(0x0B);
+    /// Only for remote commands, the command couldn't be encrypted or decrypted.
+    pub const ENCRYPTION_ERROR: Self = Self(0x0C);
+    /// Only for remote commands, the command was sent insecurely where a secure session was
+    /// required.
+    pub const SENT_INSECURELY: Self = Self(0x0D);
+}
+
+impl core::fmt::Display for AtCommandStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match *self {
+            Self::OK => "OK",
+            Self::ERROR => "ERROR",
+            Self::INVALID_COMMAND => "invalid AT command",
+            Self::INVALID_PARAMETER => "invalid AT command parameter",
+            Self::TX_FAILURE => "failed to transmit the AT command",
+            Self::NO_SECURE_SESSION => "no secure session with the remote radio",
+            Self::ENCRYPTION_ERROR => "AT command encryption error",
+            Self::SENT_INSECURELY => "AT command sent insecurely",
+            Self(status) => return write!(f, "AT command status {status:#04x}"),
+        })
+    }
+}
+
+/// The command status byte of an AT command response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AtResponseStatus {
+    pub status: AtCommandStatus,
+    /// Set when the response was sent encrypted.
+    pub encrypted: bool,
+}
+
+impl AtResponseStatus {
+    const ENCRYPTED: u8 = 0x40;
+
+    pub fn is_ok(&self) -> bool {
+        self.status == AtCommandStatus::OK
+    }
+
+    pub fn result(&self) -> Result<(), AtCommandStatus> {
+        match self.status {
+            AtCommandStatus::OK => Ok(()),
+            status => Err(status),
+        }
+    }
+}
+
+impl InnerData for AtResponseStatus {
+    const MAX_SIZE: Option<usize> = Some(1);
+    const MIN_SIZE: usize = 1;
+
+    fn byte_size(&self) -> usize {
+        Self::MIN_SIZE
+    }
+
+    fn write<S: WriteStream>(&self, stream: &mut S) {
+        let flags = if self.encrypted { Self::ENCRYPTED } else { 0 };
+        (self.status.0 | flags).write(stream);
+    }
+
+    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+        let byte = u8::read(stream, max_size)?;
+        Ok(Self {
+            status: AtCommandStatus(byte & !Self::ENCRYPTED),
+            encrypted: byte & Self::ENCRYPTED != 0,
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
@@ -23,12 +137,16 @@ impl<T: InnerData> FrameData for LocalAtCommandRequest<T> {
 pub struct LocalAtCommandResponse<T> {
     pub id: u8,
     pub at_command: [u8; 2],
-    pub command_status: u8,
+    pub command_status: AtResponseStatus,
     pub command_data: T,
 }
 
 impl<T: InnerData> FrameData for LocalAtCommandResponse<T> {
     const API_TYPE: u8 = 0x88;
+
+    fn frame_id(&self) -> Option<u8> {
+        nonzero_frame_id(self.id)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
@@ -41,6 +159,10 @@ pub struct QueueLocalAtCommandRequest<T> {
 
 impl<T: InnerData> FrameData for QueueLocalAtCommandRequest<T> {
     const API_TYPE: u8 = 0x09;
+
+    fn frame_id(&self) -> Option<u8> {
+        nonzero_frame_id(self.id)
+    }
 }
 
 bitflags! {
@@ -56,6 +178,139 @@ bitflags! {
     }
 }
 
+#[cfg(feature = "zigbee")]
+pub mod register_joining_device {
+    use super::*;
+
+    bitflags! {
+        #[derive(Default, InnerData)]
+        #[repr(transparent)]
+        pub struct RegisterOpts: u8 {
+            /// The key is an install code with its CRC, rather than a link key.
+            const INSTALL_CODE = 0x01;
+        }
+    }
+
+    /// The longest key of a [`RegisterJoiningDevice`], a 16 byte install code and its CRC.
+    pub const MAX_REGISTER_KEY_LEN: usize = 18;
+
+    /// Computes the CRC that follows an install code, which is sent little-endian.
+    pub fn install_code_crc(install_code: &[u8]) -> u16 {
+        // CRC-16/X-25
+        let mut crc = 0xffff;
+        for &byte in install_code {
+            crc ^= byte as u16;
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 {
+                    crc >> 1 ^ 0x8408
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        !crc
+    }
+
+    /// Registers a device with the trust center, so it can join with its link key or install code.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct RegisterJoiningDevice {
+        pub id: u8,
+        pub ieee_address: IeeeAddress,
+        /// Reserved, [`NetworkAddress::UNKNOWN`].
+        pub network_address: NetworkAddress,
+        pub register_opts: RegisterOpts,
+        /// The link key, or the install code with its CRC.
+        pub key: stream::HungryVec<u8, MAX_REGISTER_KEY_LEN>,
+    }
+
+    impl RegisterJoiningDevice {
+        /// Registers the device at `ieee_address` with an install code of 6, 8, 12 or 16 bytes,
+        /// appending its CRC. `None` for install codes of any other length.
+        pub fn with_install_code(
+            id: u8,
+            ieee_address: IeeeAddress,
+            install_code: &[u8],
+        ) -> Option<Self> {
+            if !matches!(install_code.len(), 6 | 8 | 12 | 16) {
+                return None;
+            }
+            let mut key = stream::HungryVec::new();
+            key.extend_from_slice(install_code).ok()?;
+            key.extend_from_slice(&install_code_crc(install_code).to_le_bytes())
+                .ok()?;
+            Some(Self {
+                id,
+                ieee_address,
+                network_address: NetworkAddress::UNKNOWN,
+                register_opts: RegisterOpts::INSTALL_CODE,
+                key,
+            })
+        }
+    }
+
+    impl FrameData for RegisterJoiningDevice {
+        const API_TYPE: u8 = 0x24;
+
+        fn frame_id(&self) -> Option<u8> {
+            nonzero_frame_id(self.id)
+        }
+    }
+
+    /// The status a [`RegisterJoiningDeviceStatus`] reports, values not listed here are passed
+    /// through as is.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+    #[repr(transparent)]
+    pub struct RegistrationStatus(pub u8);
+
+    impl RegistrationStatus {
+        pub const SUCCESS: Self = Self(0x00);
+        pub const KEY_TOO_LONG: Self = Self(0x01);
+        pub const TRANSIENT_KEY_TABLE_FULL: Self = Self(0xB1);
+        pub const ADDRESS_NOT_FOUND: Self = Self(0xB2);
+        /// Keys of all 0x00 or 0xFF bytes are reserved.
+        pub const INVALID_KEY: Self = Self(0xB3);
+        pub const INVALID_ADDRESS: Self = Self(0xB4);
+        pub const KEY_TABLE_FULL: Self = Self(0xB5);
+        /// The install code CRC doesn't match.
+        pub const INVALID_SECURITY_DATA: Self = Self(0xBD);
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct RegisterJoiningDeviceStatus {
+        pub id: u8,
+        pub status: RegistrationStatus,
+    }
+
+    impl FrameData for RegisterJoiningDeviceStatus {
+        const API_TYPE: u8 = 0xA4;
+
+        fn frame_id(&self) -> Option<u8> {
+            nonzero_frame_id(self.id)
+        }
+    }
+
+    /// Sent by the trust center when a device authenticated and joined the network.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct DeviceAuthenticatedIndicator {
+        pub source_ieee_address: IeeeAddress,
+        pub source_network_address: NetworkAddress,
+        /// 0 on success.
+        pub status: u8,
+    }
+
+    impl FrameData for DeviceAuthenticatedIndicator {
+        const API_TYPE: u8 = 0xA2;
+    }
+}
+#[cfg(feature = "zigbee")]
+pub use register_joining_device::{
+    install_code_crc, DeviceAuthenticatedIndicator, RegisterJoiningDevice,
+    RegisterJoiningDeviceStatus, RegisterOpts, RegistrationStatus, MAX_REGISTER_KEY_LEN,
+};
+
 #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
 #[repr(C)]
 pub struct TransmitRequest<T> {
@@ -69,11 +324,19 @@ pub struct TransmitRequest<T> {
 
 impl<T: InnerData> FrameData for TransmitRequest<T> {
     const API_TYPE: u8 = 0x10;
+
+    fn frame_id(&self) -> Option<u8> {
+        nonzero_frame_id(self.id)
+    }
+
+    fn unicast_destination(&self) -> Option<(IeeeAddress, NetworkAddress)> {
+        unicast(self.ieee_address, self.network_address, self.transmit_opts)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
 #[repr(C)]
-pub struct ExplicitAddressingCommandRequest {
+pub struct ExplicitAddressingCommandRequest<T> {
     pub id: u8,
     pub dest64: IeeeAddress,
     pub dest16: NetworkAddress,
@@ -83,12 +346,373 @@ pub struct ExplicitAddressingCommandRequest {
     pub profile_id: ProfileId,
     pub broadcast_radius: u8,
     pub transmit_opts: TransmitOpts,
-    pub command_data: stream::OverwriteLittleEndian<ClusterData>,
+    /// ZDO and ZCL payloads are little-endian, unlike the rest of the frame.
+    pub command_data: stream::OverwriteLittleEndian<T>,
 }
 
 impl<T: InnerData> FrameData for ExplicitAddressingCommandRequest<T> {
     const API_TYPE: u8 = 0x11;
+
+    fn frame_id(&self) -> Option<u8> {
+        nonzero_frame_id(self.id)
+    }
+
+    fn unicast_destination(&self) -> Option<(IeeeAddress, NetworkAddress)> {
+        unicast(self.dest64, self.dest16, self.transmit_opts)
+    }
+}
+
+impl<T: Cluster> ExplicitAddressingCommandRequest<T> {
+    /// Makes a request with the cluster and profile IDs of `T`, the maximum broadcast radius and
+    /// no transmit options.
+    pub fn new(
+        id: u8,
+        dest64: IeeeAddress,
+        dest16: NetworkAddress,
+        source_ep: Endpoint,
+        dest_ep: Endpoint,
+        command_data: T,
+    ) -> Self {
+        Self {
+            id,
+            dest64,
+            dest16,
+            source_ep,
+            dest_ep,
+            cluster_id: T::CLUSTER_ID,
+            profile_id: T::PROFILE_ID,
+            broadcast_radius: 0,
+            transmit_opts: TransmitOpts::empty(),
+            command_data: stream::OverwriteLittleEndian(command_data),
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Default, InnerData)]
+    #[repr(transparent)]
+    pub struct RemoteCommandOpts: u8 {
+        const DISABLE_ACK = 0x01;
+        /// Apply the change right away, otherwise it waits for an `AC` or `WR`.
+        const APPLY_CHANGES = 0x02;
+        const SECURE_SESSION_ENCRYPTION = 0x10;
+        const USE_EXTENDED_TIMEOUT = 0x40;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+#[repr(C)]
+pub struct RemoteAtCommandRequest<T> {
+    pub id: u8,
+    pub ieee_address: IeeeAddress,
+    /// [`NetworkAddress::UNKNOWN`] when addressing by `ieee_address`.
+    pub network_address: NetworkAddress,
+    pub remote_command_opts: RemoteCommandOpts,
+    pub at_command: [u8; 2],
+    pub parameter: T,
+}
+
+impl<T: InnerData> FrameData for RemoteAtCommandRequest<T> {
+    const API_TYPE: u8 = 0x17;
+
+    fn frame_id(&self) -> Option<u8> {
+        nonzero_frame_id(self.id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+#[repr(C)]
+pub struct RemoteAtCommandResponse<T> {
+    pub id: u8,
+    pub source_ieee_address: IeeeAddress,
+    pub source_network_address: NetworkAddress,
+    pub at_command: [u8; 2],
+    pub command_status: AtResponseStatus,
+    pub command_data: T,
+}
+
+impl<T: InnerData> FrameData for RemoteAtCommandResponse<T> {
+    const API_TYPE: u8 = 0x97;
+
+    fn frame_id(&self) -> Option<u8> {
+        nonzero_frame_id(self.id)
+    }
+}
+
+#[cfg(feature = "zigbee")]
+pub mod create_source_route {
+    use super::*;
+
+    /// The most intermediate hops of a source route this crate keeps.
+    pub const MAX_SOURCE_ROUTE_HOPS: usize = 40;
+
+    /// Installs a source route to a node in the radio, ahead of a transmit request to it.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct CreateSourceRoute {
+        /// Must be 0, the radio doesn't respond.
+        pub id: u8,
+        pub ieee_address: IeeeAddress,
+        pub network_address: NetworkAddress,
+        /// Must be 0.
+        pub route_command_opts: u8,
+        /// Starting with the neighbor of the destination, as in a [`RouteRecordIndicator`].
+        pub addresses: stream::SizeVec<stream::U8Len, NetworkAddress, MAX_SOURCE_ROUTE_HOPS>,
+    }
+
+    impl FrameData for CreateSourceRoute {
+        const API_TYPE: u8 = 0x21;
+    }
+}
+#[cfg(feature = "zigbee")]
+pub use create_source_route::{CreateSourceRoute, MAX_SOURCE_ROUTE_HOPS};
+
+#[cfg(feature = "ieee802154")]
+pub mod ieee802154 {
+    use super::*;
+
+    bitflags! {
+        #[derive(Default, InnerData)]
+        #[repr(transparent)]
+        pub struct TransmitOpts: u8 {
+            const DISABLE_ACK = 0x01;
+            /// Send to the broadcast PAN ID rather than `ID`.
+            const BROADCAST_PAN_ID = 0x04;
+        }
+    }
+
+    bitflags! {
+        #[derive(Default, InnerData)]
+        #[repr(transparent)]
+        pub struct ReceiveOpts: u8 {
+            const ADDRESS_BROADCAST = 0x02;
+            const PAN_BROADCAST = 0x04;
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct TransmitRequest64<T> {
+        pub id: u8,
+        pub ieee_address: IeeeAddress,
+        pub transmit_opts: TransmitOpts,
+        pub payload_data: T,
+    }
+
+    impl<T: InnerData> FrameData for TransmitRequest64<T> {
+        const API_TYPE: u8 = 0x00;
+
+        fn frame_id(&self) -> Option<u8> {
+            nonzero_frame_id(self.id)
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct TransmitRequest16<T> {
+        pub id: u8,
+        pub network_address: NetworkAddress,
+        pub transmit_opts: TransmitOpts,
+        pub payload_data: T,
+    }
+
+    impl<T: InnerData> FrameData for TransmitRequest16<T> {
+        const API_TYPE: u8 = 0x01;
+
+        fn frame_id(&self) -> Option<u8> {
+            nonzero_frame_id(self.id)
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct ReceivePacket64<const N: usize> {
+        pub source_ieee_address: IeeeAddress,
+        /// In -dBm.
+        pub rssi: u8,
+        pub receive_opts: ReceiveOpts,
+        pub received_data: stream::HungryVec<u8, N>,
+    }
+
+    impl<const N: usize> FrameData for ReceivePacket64<N> {
+        const API_TYPE: u8 = 0x80;
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct ReceivePacket16<const N: usize> {
+        pub source_network_address: NetworkAddress,
+        /// In -dBm.
+        pub rssi: u8,
+        pub receive_opts: ReceiveOpts,
+        pub received_data: stream::HungryVec<u8, N>,
+    }
+
+    impl<const N: usize> FrameData for ReceivePacket16<N> {
+        const API_TYPE: u8 = 0x81;
+    }
+}
+#[cfg(feature = "ieee802154")]
+pub use ieee802154::{ReceivePacket16, ReceivePacket64, TransmitRequest16, TransmitRequest64};
+
+pub mod transmit_status {
+    use super::*;
+
+    /// The delivery status a [`TransmitStatus`] reports, values not listed here are passed through
+    /// as is.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+    #[repr(transparent)]
+    pub struct DeliveryStatus(pub u8);
+
+    impl DeliveryStatus {
+        pub const SUCCESS: Self = Self(0x00);
+        pub const NO_ACKNOWLEDGEMENT: Self = Self(0x01);
+        pub const CCA_FAILURE: Self = Self(0x02);
+        /// An indirect message was never requested by its destination.
+        pub const PURGED: Self = Self(0x03);
+        pub const NETWORK_ACK_FAILURE: Self = Self(0x21);
+        pub const NOT_JOINED_TO_NETWORK: Self = Self(0x22);
+        pub const SELF_ADDRESSED: Self = Self(0x23);
+        pub const ADDRESS_NOT_FOUND: Self = Self(0x24);
+        pub const ROUTE_NOT_FOUND: Self = Self(0x25);
+        pub const INTERNAL_RESOURCE_ERROR: Self = Self(0x31);
+        pub const RESOURCE_ERROR: Self = Self(0x32);
+        pub const DATA_PAYLOAD_TOO_LARGE: Self = Self(0x74);
+    }
+
+    /// The transmit status of 802.15.4 style radios, Zigbee radios send an
+    /// [`ExtendedTransmitStatus`](super::ExtendedTransmitStatus) instead.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct TransmitStatus {
+        pub id: u8,
+        pub delivery_status: DeliveryStatus,
+    }
+
+    impl FrameData for TransmitStatus {
+        const API_TYPE: u8 = 0x89;
+
+        fn frame_id(&self) -> Option<u8> {
+            nonzero_frame_id(self.id)
+        }
+    }
+}
+pub use transmit_status::TransmitStatus;
+
+pub mod modem_status {
+    use super::*;
+
+    /// The status a [`ModemStatus`] reports, values not listed here are passed through as is.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+    #[repr(transparent)]
+    pub struct ModemStatusCode(pub u8);
+
+    impl ModemStatusCode {
+        pub const HARDWARE_RESET: Self = Self(0x00);
+        pub const WATCHDOG_TIMER_RESET: Self = Self(0x01);
+        pub const JOINED_NETWORK: Self = Self(0x02);
+        pub const DISASSOCIATED: Self = Self(0x03);
+        pub const CONFIGURATION_ERROR: Self = Self(0x04);
+        pub const COORDINATOR_STARTED: Self = Self(0x06);
+        pub const NETWORK_SECURITY_KEY_UPDATED: Self = Self(0x07);
+        pub const NETWORK_WOKE_UP: Self = Self(0x0B);
+        pub const NETWORK_WENT_TO_SLEEP: Self = Self(0x0C);
+        pub const VOLTAGE_SUPPLY_LIMIT_EXCEEDED: Self = Self(0x0D);
+        pub const REMOTE_MANAGER_CONNECTED: Self = Self(0x0E);
+        pub const REMOTE_MANAGER_DISCONNECTED: Self = Self(0x0F);
+        /// The configuration changed while a join was in progress.
+        pub const CONFIGURATION_CHANGED_WHILE_JOINING: Self = Self(0x11);
+        pub const ACCESS_FAULT: Self = Self(0x12);
+        pub const FATAL_ERROR: Self = Self(0x13);
+        pub const BLUETOOTH_CONNECTED: Self = Self(0x32);
+        pub const BLUETOOTH_DISCONNECTED: Self = Self(0x33);
+        pub const BANDMASK_CONFIGURATION_FAILED: Self = Self(0x34);
+        pub const FIRMWARE_UPDATE_STARTED: Self = Self(0x38);
+        pub const FIRMWARE_UPDATE_FAILED: Self = Self(0x39);
+        pub const FIRMWARE_UPDATE_APPLYING: Self = Self(0x3A);
+        pub const SECURE_SESSION_ESTABLISHED: Self = Self(0x3B);
+        pub const SECURE_SESSION_ENDED: Self = Self(0x3C);
+        pub const SECURE_SESSION_AUTHENTICATION_FAILED: Self = Self(0x3D);
+        /// The coordinator detected a PAN ID conflict but `CR` is 0.
+        pub const PAN_ID_CONFLICT: Self = Self(0x3E);
+        pub const COORDINATOR_CHANGED_PAN_ID: Self = Self(0x3F);
+        pub const ROUTER_PAN_ID_CHANGED_BY_COORDINATOR: Self = Self(0x40);
+        pub const NETWORK_WATCHDOG_TIMEOUT_EXPIRED: Self = Self(0x42);
+
+        /// Values from 0x80 up are errors reported by the network stack itself.
+        pub fn is_stack_error(&self) -> bool {
+            0x80 <= self.0
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct ModemStatus {
+        pub status: ModemStatusCode,
+    }
+
+    impl FrameData for ModemStatus {
+        const API_TYPE: u8 = 0x8A;
+    }
+}
+pub use modem_status::ModemStatus;
+
+#[cfg(feature = "digimesh")]
+pub mod digimesh {
+    use super::*;
+
+    /// The event a [`RouteInformation`] reports, values not listed here are passed through as is.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+    #[repr(transparent)]
+    pub struct RouteInformationEvent(pub u8);
+
+    impl RouteInformationEvent {
+        /// A hop failed to deliver a unicast sent with NACK enabled.
+        pub const NACK: Self = Self(0x11);
+        /// A hop relayed a unicast sent with trace route enabled.
+        pub const TRACE_ROUTE: Self = Self(0x12);
+    }
+
+    /// Sent for each hop of a DigiMesh unicast with trace route or NACK enabled.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct RouteInformation {
+        pub source_event: RouteInformationEvent,
+        /// The number of bytes that follow.
+        pub length: u8,
+        /// The system timer of the responder in µs.
+        pub timestamp: u32,
+        pub ack_timeout_count: u8,
+        pub tx_blocked_count: u8,
+        pub reserved: u8,
+        pub destination_ieee_address: IeeeAddress,
+        pub source_ieee_address: IeeeAddress,
+        /// The node that sent this report.
+        pub responder_ieee_address: IeeeAddress,
+        /// The node the responder relayed to, or failed to.
+        pub receiver_ieee_address: IeeeAddress,
+    }
+
+    impl FrameData for RouteInformation {
+        const API_TYPE: u8 = 0x8D;
+    }
+
+    /// Sent when `DH`/`DL` changed by an aggregate addressing broadcast (`AG`).
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct AggregateAddressingUpdate {
+        /// Always 0.
+        pub format_id: u8,
+        pub new_address: IeeeAddress,
+        pub old_address: IeeeAddress,
+    }
+
+    impl FrameData for AggregateAddressingUpdate {
+        const API_TYPE: u8 = 0x8E;
+    }
 }
+#[cfg(feature = "digimesh")]
+pub use digimesh::{AggregateAddressingUpdate, RouteInformation};
 
 pub mod extended_transmit_status {
     use super::*;
@@ -141,27 +765,56 @@ pub mod extended_transmit_status {
 
     impl FrameData for ExtendedTransmitStatus {
         const API_TYPE: u8 = 0x8B;
+
+        fn frame_id(&self) -> Option<u8> {
+            nonzero_frame_id(self.id)
+        }
     }
 }
 pub use extended_transmit_status::ExtendedTransmitStatus;
 
-pub mod explicit_rx_indicator {
+pub mod receive_packet {
+    pub use super::explicit_rx_indicator::ReceiveOpts;
     use super::*;
 
-    bitflags! {
-        #[derive(Default, InnerData)]
-        pub struct ReceiveOpts: u8 {
-            const ACKNOWLEDGED = 1 << 0;
-            const SENT_AS_BROADCAST = 1 << 1;
-            const SENT_SECURELY = 1 << 4;
-            const ENCRYPTED_WITH_ZIGBEE_APS = 1 << 5;
-            const SENT_FROM_END_DEVICE = 1 << 6;
-        }
-    }
+    /// The received data frame with the default `AO=0`, [`ExplicitRxIndicator`] replaces it
+    /// with `AO=1`.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct ReceivePacket<const N: usize> {
+        pub source_ieee_address: IeeeAddress,
+        pub source_network_address: NetworkAddress,
+        pub receive_opts: ReceiveOpts,
+        pub received_data: stream::HungryVec<u8, N>,
+    }
+
+    impl<const N: usize> FrameData for ReceivePacket<N> {
+        const API_TYPE: u8 = 0x90;
+    }
+}
+pub use receive_packet::ReceivePacket;
+
+pub mod explicit_rx_indicator {
+    use super::*;
+    #[cfg(feature = "zigbee")]
+    use crate::zdo::{self, ZdoFrame};
+    #[cfg(feature = "zigbee")]
+    use crate::zha::ZclFrame;
+
+    bitflags! {
+        #[derive(Default, InnerData)]
+        pub struct ReceiveOpts: u8 {
+            const ACKNOWLEDGED = 1 << 0;
+            const SENT_AS_BROADCAST = 1 << 1;
+            const SENT_SECURELY = 1 << 4;
+            const ENCRYPTED_WITH_ZIGBEE_APS = 1 << 5;
+            const SENT_FROM_END_DEVICE = 1 << 6;
+        }
+    }
 
     #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
     #[repr(C)]
-    pub struct ExplicitRxIndicator {
+    pub struct ExplicitRxIndicator<const N: usize> {
         pub source_ieee_address: IeeeAddress,
         pub source_network_address: NetworkAddress,
         pub source_endpoint: Endpoint,
@@ -169,15 +822,493 @@ pub mod explicit_rx_indicator {
         pub cluster_id: ClusterId,
         pub profile_id: ProfileId,
         pub receive_opts: ReceiveOpts,
-        pub received_data: ClusterData,
+        pub received_data: stream::HungryVec<u8, N>,
     }
 
-    impl FrameData for ExplicitRxIndicator {
+    impl<const N: usize> FrameData for ExplicitRxIndicator<N> {
         const API_TYPE: u8 = 0x91;
     }
+
+    #[cfg(feature = "zigbee")]
+    impl<const N: usize> ExplicitRxIndicator<N> {
+        pub fn cluster_data(&self) -> Result<ClusterData<N>, DecodeError> {
+            let stream =
+                &mut stream::SliceReader::new(&self.received_data, Endianness::LittleEndian);
+            ClusterData::read(
+                self.profile_id,
+                self.cluster_id,
+                stream,
+                self.received_data.len(),
+            )
+        }
+    }
+
+    /// `received_data` decoded according to the profile and cluster it was sent to.
+    #[cfg(feature = "zigbee")]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ClusterData<const N: usize> {
+        NetworkAddressResponse(ZdoFrame<zdo::NetworkAddressResponse>),
+        IeeeAddressResponse(ZdoFrame<zdo::IeeeAddressResponse>),
+        NodeDescriptorResponse(ZdoFrame<zdo::NodeDescriptorResponse>),
+        SimpleDescriptorResponse(ZdoFrame<zdo::SimpleDescriptorResponse>),
+        ActiveEndpointsResponse(ZdoFrame<zdo::ActiveEndpointsResponse>),
+        MatchDescriptorResponse(ZdoFrame<zdo::MatchDescriptorResponse>),
+        ComplexDescriptorResponse(ZdoFrame<zdo::ComplexDescriptorResponse>),
+        Zcl(ZclFrame<N>),
+        Raw(stream::HungryVec<u8, N>),
+    }
+
+    #[cfg(feature = "zigbee")]
+    impl<const N: usize> ClusterData<N> {
+        /// Reads a little-endian payload sent to `profile_id` and `cluster_id`.
+        pub fn read<S: ReadStream>(
+            profile_id: ProfileId,
+            cluster_id: ClusterId,
+            stream: &mut S,
+            max_size: usize,
+        ) -> Result<Self, DecodeError> {
+            use zdo::*;
+
+            let stream = &mut stream::OverwriteLittleEndian(stream);
+            Ok(match (profile_id, cluster_id) {
+                (ProfileId::ZIGBEE_DEVICE, NetworkAddressResponse::CLUSTER_ID) => {
+                    Self::NetworkAddressResponse(InnerData::read(stream, max_size)?)
+                }
+                (ProfileId::ZIGBEE_DEVICE, IeeeAddressResponse::CLUSTER_ID) => {
+                    Self::IeeeAddressResponse(InnerData::read(stream, max_size)?)
+                }
+                (ProfileId::ZIGBEE_DEVICE, NodeDescriptorResponse::CLUSTER_ID) => {
+                    Self::NodeDescriptorResponse(InnerData::read(stream, max_size)?)
+                }
+                (ProfileId::ZIGBEE_DEVICE, SimpleDescriptorResponse::CLUSTER_ID) => {
+                    Self::SimpleDescriptorResponse(InnerData::read(stream, max_size)?)
+                }
+                (ProfileId::ZIGBEE_DEVICE, ActiveEndpointsResponse::CLUSTER_ID) => {
+                    Self::ActiveEndpointsResponse(InnerData::read(stream, max_size)?)
+                }
+                (ProfileId::ZIGBEE_DEVICE, MatchDescriptorResponse::CLUSTER_ID) => {
+                    Self::MatchDescriptorResponse(InnerData::read(stream, max_size)?)
+                }
+                (ProfileId::ZIGBEE_DEVICE, ComplexDescriptorResponse::CLUSTER_ID) => {
+                    Self::ComplexDescriptorResponse(InnerData::read(stream, max_size)?)
+                }
+                (
+                    ProfileId::HOME_AUTOMATION | ProfileId::SMART_ENERGY | ProfileId::LIGHT_LINK,
+                    _,
+                ) => Self::Zcl(InnerData::read(stream, max_size)?),
+                _ => Self::Raw(InnerData::read(stream, max_size)?),
+            })
+        }
+    }
 }
+#[cfg(feature = "zigbee")]
+pub use explicit_rx_indicator::ClusterData;
 pub use explicit_rx_indicator::ExplicitRxIndicator;
 
+pub mod io_data_sample_rx_indicator {
+    pub use super::explicit_rx_indicator::ReceiveOpts;
+    use super::*;
+    use heapless::Vec;
+
+    bitflags! {
+        #[derive(Default, InnerData)]
+        #[repr(transparent)]
+        pub struct DigitalChannels: u16 {
+            const DIO0 = 1 << 0;
+            const DIO1 = 1 << 1;
+            const DIO2 = 1 << 2;
+            const DIO3 = 1 << 3;
+            const DIO4 = 1 << 4;
+            const DIO5 = 1 << 5;
+            const DIO6 = 1 << 6;
+            const DIO7 = 1 << 7;
+            const DIO10 = 1 << 10;
+            const DIO11 = 1 << 11;
+            const DIO12 = 1 << 12;
+        }
+    }
+
+    bitflags! {
+        #[derive(Default, InnerData)]
+        #[repr(transparent)]
+        pub struct AnalogChannels: u8 {
+            const AD0 = 1 << 0;
+            const AD1 = 1 << 1;
+            const AD2 = 1 << 2;
+            const AD3 = 1 << 3;
+            const SUPPLY_VOLTAGE = 1 << 7;
+        }
+    }
+
+    /// The ADC reference of Zigbee radios before XBee 3, which set it with `AV`.
+    pub const ADC_REFERENCE_MV: u16 = 1200;
+
+    /// Converts a 10 bit ADC reading to millivolts against a reference of `reference_mv`,
+    /// rounded to the nearest.
+    pub fn adc_to_millivolts(counts: u16, reference_mv: u16) -> u16 {
+        ((counts as u32 * reference_mv as u32 + 1023 / 2) / 1023) as u16
+    }
+
+    /// The pins sampled at once, in the order the radio sends them.
+    #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+    pub struct IoSample {
+        pub digital_channels: DigitalChannels,
+        pub analog_channels: AnalogChannels,
+        /// One bit per pin of `digital_channels`, only sent when it isn't empty.
+        pub digital_samples: u16,
+        /// One reading per channel of `analog_channels`, from the lowest bit up.
+        pub analog_samples: Vec<u16, 5>,
+    }
+
+    impl IoSample {
+        /// The level of `DIO<pin>`, `None` if it isn't sampled.
+        pub fn digital(&self, pin: u8) -> Option<bool> {
+            let bit = 1u16.checked_shl(pin as u32)?;
+            (self.digital_channels.bits() & bit != 0).then_some(self.digital_samples & bit != 0)
+        }
+
+        /// The ADC reading of `AD<pin>`, `None` if it isn't sampled.
+        pub fn analog(&self, pin: u8) -> Option<u16> {
+            let bit = 1u8.checked_shl(pin as u32)?;
+            let channels = self.analog_channels.bits();
+            if channels & bit == 0 {
+                return None;
+            }
+            let index = (channels & (bit - 1)).count_ones() as usize;
+            self.analog_samples.get(index).copied()
+        }
+
+        pub fn analog_millivolts(&self, pin: u8, reference_mv: u16) -> Option<u16> {
+            Some(adc_to_millivolts(self.analog(pin)?, reference_mv))
+        }
+
+        /// The ADC reading of the supply voltage, sent when it drops under `V+`.
+        pub fn supply_voltage(&self) -> Option<u16> {
+            self.analog(7)
+        }
+    }
+
+    impl InnerData for IoSample {
+        const MAX_SIZE: Option<usize> = Some(2 + 1 + 2 + 5 * 2);
+        const MIN_SIZE: usize = 2 + 1;
+
+        fn byte_size(&self) -> usize {
+            let digital_size = if self.digital_channels.is_empty() {
+                0
+            } else {
+                2
+            };
+            Self::MIN_SIZE + digital_size + 2 * self.analog_samples.len()
+        }
+
+        fn write<S: WriteStream>(&self, stream: &mut S) {
+            self.digital_channels.write(stream);
+            self.analog_channels.write(stream);
+            if !self.digital_channels.is_empty() {
+                self.digital_samples.write(stream);
+            }
+            for sample in &self.analog_samples {
+                sample.write(stream);
+            }
+        }
+
+        fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+            let digital_channels = DigitalChannels::read(stream, max_size)?;
+            let analog_channels = AnalogChannels::read(stream, max_size - 2)?;
+            let mut size_remaining = max_size - Self::MIN_SIZE;
+
+            let mut digital_samples = 0;
+            if !digital_channels.is_empty() {
+                digital_samples = u16::read(stream, size_remaining)?;
+                size_remaining -= 2;
+            }
+            let mut analog_samples = Vec::new();
+            for _ in 0..analog_channels.bits().count_ones() {
+                let sample = u16::read(stream, size_remaining)?;
+                size_remaining -= 2;
+                analog_samples
+                    .push(sample)
+                    .map_err(|_| DecodeError::CapacityOverflow)?;
+            }
+
+            Ok(Self {
+                digital_channels,
+                analog_channels,
+                digital_samples,
+                analog_samples,
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct IoDataSampleRxIndicator {
+        pub source_ieee_address: IeeeAddress,
+        pub source_network_address: NetworkAddress,
+        pub receive_opts: ReceiveOpts,
+        /// Always 1.
+        pub number_of_samples: u8,
+        pub sample: IoSample,
+    }
+
+    impl FrameData for IoDataSampleRxIndicator {
+        const API_TYPE: u8 = 0x92;
+    }
+}
+pub use io_data_sample_rx_indicator::IoDataSampleRxIndicator;
+
+pub mod sensor_read_indicator {
+    pub use super::explicit_rx_indicator::ReceiveOpts;
+    use super::*;
+
+    bitflags! {
+        #[derive(Default, InnerData)]
+        #[repr(transparent)]
+        pub struct OneWireSensors: u8 {
+            const AD_SENSOR_READ = 0x01;
+            const TEMPERATURE_SENSOR_READ = 0x02;
+            const WATER_PRESENT = 0x60;
+        }
+    }
+
+    /// Sent by the Digi 1-wire sensor adapters.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct SensorReadIndicator {
+        pub source_ieee_address: IeeeAddress,
+        pub source_network_address: NetworkAddress,
+        pub receive_opts: ReceiveOpts,
+        pub one_wire_sensors: OneWireSensors,
+        /// The 8 bit readings of A/D channels A to D over 5.1 V, 0xffff where there's none.
+        pub ad_values: [u16; 4],
+        /// 12 bit two's complement in 1/16 °C, 0xffff if there's no temperature sensor.
+        pub temperature: u16,
+    }
+
+    impl SensorReadIndicator {
+        pub const NOT_PRESENT: u16 = 0xffff;
+
+        pub fn temperature_celsius(&self) -> Option<f32> {
+            if self.temperature == Self::NOT_PRESENT {
+                return None;
+            }
+            let sixteenths = ((self.temperature << 4) as i16) >> 4;
+            Some(sixteenths as f32 / 16.0)
+        }
+
+        /// The reading of A/D channel `channel` (0 for A to 3 for D) in mV.
+        pub fn millivolts(&self, channel: usize) -> Option<u16> {
+            let value = *self.ad_values.get(channel)?;
+            (value != Self::NOT_PRESENT).then(|| (value as u32 * 5100 / 255) as u16)
+        }
+
+        /// The relative humidity in % from the HIH-4000 sensor on `channel` powered by the
+        /// supply on `supply_channel`, compensated for temperature when there's a reading.
+        pub fn relative_humidity(&self, channel: usize, supply_channel: usize) -> Option<f32> {
+            let output = self.millivolts(channel)? as f32;
+            let supply = self.millivolts(supply_channel)? as f32;
+            let humidity = (output / supply - 0.16) / 0.0062;
+            Some(match self.temperature_celsius() {
+                Some(celsius) => humidity / (1.0546 - 0.00216 * celsius),
+                None => humidity,
+            })
+        }
+
+        /// The light level in lux from the light sensor on `channel`, which outputs 1 mV per
+        /// lux on the Digi adapters.
+        pub fn light_lux(&self, channel: usize) -> Option<u16> {
+            self.millivolts(channel)
+        }
+    }
+
+    impl FrameData for SensorReadIndicator {
+        const API_TYPE: u8 = 0x94;
+    }
+}
+pub use sensor_read_indicator::SensorReadIndicator;
+
+pub mod node_identification_indicator {
+    pub use super::explicit_rx_indicator::ReceiveOpts;
+    use super::*;
+    use crate::at::NODE_IDENTIFIER_LEN;
+
+    /// The role of the node that identified itself, values not listed here are passed through
+    /// as is.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+    #[repr(transparent)]
+    pub struct DeviceType(pub u8);
+
+    impl DeviceType {
+        pub const COORDINATOR: Self = Self(0x00);
+        pub const ROUTER: Self = Self(0x01);
+        pub const END_DEVICE: Self = Self(0x02);
+    }
+
+    /// What made the node identify itself, values not listed here are passed through as is.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+    #[repr(transparent)]
+    pub struct SourceEvent(pub u8);
+
+    impl SourceEvent {
+        /// The commissioning button was pressed once, or `CB1` ran.
+        pub const PUSH_BUTTON: Self = Self(0x01);
+        pub const JOINED: Self = Self(0x02);
+        pub const POWER_CYCLE: Self = Self(0x03);
+    }
+
+    /// Sent when a node identifies itself, e.g. after its commissioning button is pressed.
+    ///
+    /// The sender is the radio that relayed the identification, the remote the node that
+    /// identified itself. Both are the same unless the frame came through a parent.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct NodeIdentificationIndicator {
+        pub source_ieee_address: IeeeAddress,
+        pub source_network_address: NetworkAddress,
+        pub receive_opts: ReceiveOpts,
+        pub remote_network_address: NetworkAddress,
+        pub remote_ieee_address: IeeeAddress,
+        pub node_identifier: stream::NulString<NODE_IDENTIFIER_LEN>,
+        pub parent_network_address: NetworkAddress,
+        pub device_type: DeviceType,
+        pub source_event: SourceEvent,
+        pub digi_profile_id: ProfileId,
+        pub manufacturer_id: u16,
+        /// The `DD` device type identifier and the RSSI of the last hop, sent when `NO` asks
+        /// for them.
+        pub appended: stream::HungryVec<u8, 5>,
+    }
+
+    impl NodeIdentificationIndicator {
+        pub fn device_type_identifier(&self) -> Option<u32> {
+            let bytes = self.appended.get(..4)?;
+            Some(u32::from_be_bytes(bytes.try_into().unwrap()))
+        }
+
+        /// In -dBm.
+        pub fn rssi(&self) -> Option<u8> {
+            self.appended.get(4).copied()
+        }
+    }
+
+    impl FrameData for NodeIdentificationIndicator {
+        const API_TYPE: u8 = 0x95;
+    }
+}
+pub use node_identification_indicator::NodeIdentificationIndicator;
+
+pub mod extended_modem_status {
+    use super::*;
+
+    /// The step of a join that an [`ExtendedModemStatus`] reports, sent when verbose join
+    /// information is enabled with `DC`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+    #[repr(transparent)]
+    pub struct JoinStatusCode(pub u8);
+
+    impl JoinStatusCode {
+        pub const REJOIN: Self = Self(0x00);
+        pub const STACK_STATUS: Self = Self(0x01);
+        pub const JOINING: Self = Self(0x02);
+        pub const JOINED: Self = Self(0x03);
+        pub const BEACON_RESPONSE: Self = Self(0x04);
+        pub const REJECT_STACK_PROFILE: Self = Self(0x05);
+        pub const REJECT_PAN_ID: Self = Self(0x06);
+        pub const REJECT_NOT_JOINABLE: Self = Self(0x07);
+        pub const PAN_ID_MATCH: Self = Self(0x08);
+        pub const REJECT_LINK_QUALITY: Self = Self(0x09);
+        pub const BEACON_SAVED: Self = Self(0x0A);
+        pub const ASSOCIATION_INDICATION_CHANGED: Self = Self(0x0B);
+        pub const PERMIT_JOIN: Self = Self(0x0C);
+        pub const SCANNING: Self = Self(0x0D);
+        pub const SCAN_ERROR: Self = Self(0x0E);
+        pub const JOIN_REQUEST: Self = Self(0x0F);
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct ExtendedModemStatus<const N: usize> {
+        pub status: JoinStatusCode,
+        /// Depends on `status`, e.g. the beacon fields for [`JoinStatusCode::BEACON_RESPONSE`].
+        pub status_data: stream::HungryVec<u8, N>,
+    }
+
+    impl<const N: usize> FrameData for ExtendedModemStatus<N> {
+        const API_TYPE: u8 = 0x98;
+    }
+}
+pub use extended_modem_status::ExtendedModemStatus;
+
+#[cfg(feature = "zigbee")]
+pub mod ota_firmware_update_status {
+    pub use super::explicit_rx_indicator::ReceiveOpts;
+    use super::*;
+
+    /// The bootloader message an [`OtaFirmwareUpdateStatus`] reports, values not listed here are
+    /// passed through as is.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+    #[repr(transparent)]
+    pub struct BootloaderMessageType(pub u8);
+
+    impl BootloaderMessageType {
+        /// The target acknowledged the block.
+        pub const ACK: Self = Self(0x06);
+        /// The target rejected the block.
+        pub const NACK: Self = Self(0x15);
+        /// The target didn't acknowledge at the MAC layer.
+        pub const NO_MAC_ACK: Self = Self(0x40);
+        /// The bootloader of the target sent a query.
+        pub const QUERY: Self = Self(0x51);
+        /// The bootloader of the target answered a query.
+        pub const QUERY_RESPONSE: Self = Self(0x52);
+    }
+
+    /// Sent by the radio relaying an over the air firmware update, when the target responds
+    /// or fails to.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct OtaFirmwareUpdateStatus {
+        pub source_ieee_address: IeeeAddress,
+        pub source_network_address: NetworkAddress,
+        pub receive_opts: ReceiveOpts,
+        pub bootloader_message_type: BootloaderMessageType,
+        pub block_number: u8,
+        pub target_ieee_address: IeeeAddress,
+    }
+
+    impl FrameData for OtaFirmwareUpdateStatus {
+        const API_TYPE: u8 = 0xA0;
+    }
+}
+#[cfg(feature = "zigbee")]
+pub use ota_firmware_update_status::OtaFirmwareUpdateStatus;
+
+#[cfg(feature = "zigbee")]
+pub mod route_record_indicator {
+    pub use super::explicit_rx_indicator::ReceiveOpts;
+    use super::*;
+
+    /// The route a unicast from a remote node took to this one, sent when the remote node
+    /// has many-to-one routing set up to this one.
+    #[derive(Debug, Clone, PartialEq, Eq, InnerData)]
+    #[repr(C)]
+    pub struct RouteRecordIndicator {
+        pub source_ieee_address: IeeeAddress,
+        pub source_network_address: NetworkAddress,
+        pub receive_opts: ReceiveOpts,
+        /// The intermediate hops, starting with the neighbor of the source node. That's the order
+        /// a [`CreateSourceRoute`] back to the source takes them in.
+        pub addresses: stream::SizeVec<stream::U8Len, NetworkAddress, MAX_SOURCE_ROUTE_HOPS>,
+    }
+
+    impl FrameData for RouteRecordIndicator {
+        const API_TYPE: u8 = 0xA1;
+    }
+}
+#[cfg(feature = "zigbee")]
+pub use route_record_indicator::RouteRecordIndicator;
+
+#[cfg(feature = "zigbee")]
 mod many_to_one_route_request_indicator {
     use super::*;
 
@@ -198,13 +1329,40 @@ mod many_to_one_route_request_indicator {
         const API_TYPE: u8 = 0xA3;
     }
 }
+#[cfg(feature = "zigbee")]
 pub use many_to_one_route_request_indicator::ManyToOneRouteRequestIndicator;
 
+pub const START_DELIMITER: u8 = 0x7e;
+pub const ESCAPE: u8 = 0x7d;
+pub const XON: u8 = 0x11;
+pub const XOFF: u8 = 0x13;
+
+/// The framing used on the serial link, as set with `AP`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiMode {
+    /// `AP=1`
+    #[default]
+    Unescaped,
+    /// `AP=2`, escapes 0x7E, 0x7D, 0x11 and 0x13 after the start delimiter.
+    Escaped,
+}
+
+impl ApiMode {
+    #[inline]
+    pub fn needs_escape(byte: u8) -> bool {
+        matches!(byte, START_DELIMITER | ESCAPE | XON | XOFF)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Frame<T: FrameData>(pub T);
 
 impl<T: FrameData> Frame<T> {
     pub fn write<F: FnMut(&[u8])>(&self, write_f: &mut F) {
+        self.write_mode(ApiMode::Unescaped, write_f);
+    }
+
+    pub fn write_mode<F: FnMut(&[u8])>(&self, mode: ApiMode, write_f: &mut F) {
         struct ApiWriteStream<F>(F);
 
         impl<F: FnMut(&[u8])> WriteStream for ApiWriteStream<F> {
@@ -216,9 +1374,20 @@ impl<T: FrameData> Frame<T> {
             }
         }
 
-        let stream = &mut ApiWriteStream(write_f);
-        stream.write(&[0x7e]);
-        (self.0.byte_size() + 1).write(stream);
+        write_f(&[START_DELIMITER]);
+        let stream = &mut ApiWriteStream(|bytes: &[u8]| match mode {
+            ApiMode::Unescaped => write_f(bytes),
+            ApiMode::Escaped => {
+                for &byte in bytes {
+                    if ApiMode::needs_escape(byte) {
+                        write_f(&[ESCAPE, byte ^ 0x20]);
+                    } else {
+                        write_f(&[byte]);
+                    }
+                }
+            }
+        });
+        ((self.0.byte_size() + 1) as u16).write(stream);
 
         let mut checksum = 0xffu8;
         let cs_stream = &mut ApiWriteStream(|bytes: &[u8]| {
@@ -233,3 +1402,503 @@ impl<T: FrameData> Frame<T> {
         stream.write(&[checksum]);
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawFrame<'a> {
+    pub api_type: u8,
+    pub payload: stream::SliceReader<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceivedFrame<const N: usize> {
+    #[cfg(feature = "ieee802154")]
+    ReceivePacket64(ReceivePacket64<N>),
+    #[cfg(feature = "ieee802154")]
+    ReceivePacket16(ReceivePacket16<N>),
+    LocalAtCommandResponse(LocalAtCommandResponse<stream::HungryVec<u8, N>>),
+    TransmitStatus(TransmitStatus),
+    ModemStatus(ModemStatus),
+    ExtendedTransmitStatus(ExtendedTransmitStatus),
+    #[cfg(feature = "digimesh")]
+    RouteInformation(RouteInformation),
+    #[cfg(feature = "digimesh")]
+    AggregateAddressingUpdate(AggregateAddressingUpdate),
+    RemoteAtCommandResponse(RemoteAtCommandResponse<stream::HungryVec<u8, N>>),
+    ReceivePacket(ReceivePacket<N>),
+    ExplicitRxIndicator(ExplicitRxIndicator<N>),
+    IoDataSampleRxIndicator(IoDataSampleRxIndicator),
+    SensorReadIndicator(SensorReadIndicator),
+    NodeIdentificationIndicator(NodeIdentificationIndicator),
+    ExtendedModemStatus(ExtendedModemStatus<N>),
+    #[cfg(feature = "zigbee")]
+    OtaFirmwareUpdateStatus(OtaFirmwareUpdateStatus),
+    #[cfg(feature = "zigbee")]
+    RouteRecordIndicator(RouteRecordIndicator),
+    #[cfg(feature = "zigbee")]
+    DeviceAuthenticatedIndicator(DeviceAuthenticatedIndicator),
+    #[cfg(feature = "zigbee")]
+    ManyToOneRouteRequestIndicator(ManyToOneRouteRequestIndicator),
+    #[cfg(feature = "zigbee")]
+    RegisterJoiningDeviceStatus(RegisterJoiningDeviceStatus),
+    Unknown {
+        api_type: u8,
+        raw: stream::HungryVec<u8, N>,
+    },
+}
+
+impl<const N: usize> ReceivedFrame<N> {
+    pub fn api_type(&self) -> u8 {
+        match self {
+            #[cfg(feature = "ieee802154")]
+            Self::ReceivePacket64(_) => ReceivePacket64::<N>::API_TYPE,
+            #[cfg(feature = "ieee802154")]
+            Self::ReceivePacket16(_) => ReceivePacket16::<N>::API_TYPE,
+            Self::LocalAtCommandResponse(_) => {
+                LocalAtCommandResponse::<stream::HungryVec<u8, N>>::API_TYPE
+            }
+            Self::TransmitStatus(_) => TransmitStatus::API_TYPE,
+            Self::ModemStatus(_) => ModemStatus::API_TYPE,
+            Self::ExtendedTransmitStatus(_) => ExtendedTransmitStatus::API_TYPE,
+            #[cfg(feature = "digimesh")]
+            Self::RouteInformation(_) => RouteInformation::API_TYPE,
+            #[cfg(feature = "digimesh")]
+            Self::AggregateAddressingUpdate(_) => AggregateAddressingUpdate::API_TYPE,
+            Self::RemoteAtCommandResponse(_) => {
+                RemoteAtCommandResponse::<stream::HungryVec<u8, N>>::API_TYPE
+            }
+            Self::ReceivePacket(_) => ReceivePacket::<N>::API_TYPE,
+            Self::ExplicitRxIndicator(_) => ExplicitRxIndicator::<N>::API_TYPE,
+            Self::IoDataSampleRxIndicator(_) => IoDataSampleRxIndicator::API_TYPE,
+            Self::SensorReadIndicator(_) => SensorReadIndicator::API_TYPE,
+            Self::NodeIdentificationIndicator(_) => NodeIdentificationIndicator::API_TYPE,
+            Self::ExtendedModemStatus(_) => ExtendedModemStatus::<N>::API_TYPE,
+            #[cfg(feature = "zigbee")]
+            Self::OtaFirmwareUpdateStatus(_) => OtaFirmwareUpdateStatus::API_TYPE,
+            #[cfg(feature = "zigbee")]
+            Self::RouteRecordIndicator(_) => RouteRecordIndicator::API_TYPE,
+            #[cfg(feature = "zigbee")]
+            Self::DeviceAuthenticatedIndicator(_) => DeviceAuthenticatedIndicator::API_TYPE,
+            #[cfg(feature = "zigbee")]
+            Self::ManyToOneRouteRequestIndicator(_) => ManyToOneRouteRequestIndicator::API_TYPE,
+            #[cfg(feature = "zigbee")]
+            Self::RegisterJoiningDeviceStatus(_) => RegisterJoiningDeviceStatus::API_TYPE,
+            Self::Unknown { api_type, .. } => *api_type,
+        }
+    }
+
+    pub fn frame_id(&self) -> Option<u8> {
+        match self {
+            #[cfg(feature = "ieee802154")]
+            Self::ReceivePacket64(frame) => frame.frame_id(),
+            #[cfg(feature = "ieee802154")]
+            Self::ReceivePacket16(frame) => frame.frame_id(),
+            Self::LocalAtCommandResponse(frame) => frame.frame_id(),
+            Self::TransmitStatus(frame) => frame.frame_id(),
+            Self::ModemStatus(frame) => frame.frame_id(),
+            Self::ExtendedTransmitStatus(frame) => frame.frame_id(),
+            #[cfg(feature = "digimesh")]
+            Self::RouteInformation(frame) => frame.frame_id(),
+            #[cfg(feature = "digimesh")]
+            Self::AggregateAddressingUpdate(frame) => frame.frame_id(),
+            Self::RemoteAtCommandResponse(frame) => frame.frame_id(),
+            Self::ReceivePacket(frame) => frame.frame_id(),
+            Self::ExplicitRxIndicator(frame) => frame.frame_id(),
+            Self::IoDataSampleRxIndicator(frame) => frame.frame_id(),
+            Self::SensorReadIndicator(frame) => frame.frame_id(),
+            Self::NodeIdentificationIndicator(frame) => frame.frame_id(),
+            Self::ExtendedModemStatus(frame) => frame.frame_id(),
+            #[cfg(feature = "zigbee")]
+            Self::OtaFirmwareUpdateStatus(frame) => frame.frame_id(),
+            #[cfg(feature = "zigbee")]
+            Self::RouteRecordIndicator(frame) => frame.frame_id(),
+            #[cfg(feature = "zigbee")]
+            Self::DeviceAuthenticatedIndicator(frame) => frame.frame_id(),
+            #[cfg(feature = "zigbee")]
+            Self::ManyToOneRouteRequestIndicator(frame) => frame.frame_id(),
+            #[cfg(feature = "zigbee")]
+            Self::RegisterJoiningDeviceStatus(frame) => frame.frame_id(),
+            Self::Unknown { .. } => None,
+        }
+    }
+
+    /// Reads the frame data following the API type byte, `max_size` being its length.
+    pub fn read<S: ReadStream>(
+        api_type: u8,
+        stream: &mut S,
+        max_size: usize,
+    ) -> Result<Self, DecodeError> {
+        Ok(match api_type {
+            #[cfg(feature = "ieee802154")]
+            x if x == ReceivePacket64::<N>::API_TYPE => {
+                Self::ReceivePacket64(InnerData::read(stream, max_size)?)
+            }
+            #[cfg(feature = "ieee802154")]
+            x if x == ReceivePacket16::<N>::API_TYPE => {
+                Self::ReceivePacket16(InnerData::read(stream, max_size)?)
+            }
+            x if x == LocalAtCommandResponse::<stream::HungryVec<u8, N>>::API_TYPE => {
+                Self::LocalAtCommandResponse(InnerData::read(stream, max_size)?)
+            }
+            TransmitStatus::API_TYPE => Self::TransmitStatus(InnerData::read(stream, max_size)?),
+            ModemStatus::API_TYPE => Self::ModemStatus(InnerData::read(stream, max_size)?),
+            ExtendedTransmitStatus::API_TYPE => {
+                Self::ExtendedTransmitStatus(InnerData::read(stream, max_size)?)
+            }
+            #[cfg(feature = "digimesh")]
+            RouteInformation::API_TYPE => {
+                Self::RouteInformation(InnerData::read(stream, max_size)?)
+            }
+            #[cfg(feature = "digimesh")]
+            AggregateAddressingUpdate::API_TYPE => {
+                Self::AggregateAddressingUpdate(InnerData::read(stream, max_size)?)
+            }
+            x if x == RemoteAtCommandResponse::<stream::HungryVec<u8, N>>::API_TYPE => {
+                Self::RemoteAtCommandResponse(InnerData::read(stream, max_size)?)
+            }
+            x if x == ReceivePacket::<N>::API_TYPE => {
+                Self::ReceivePacket(InnerData::read(stream, max_size)?)
+            }
+            x if x == ExplicitRxIndicator::<N>::API_TYPE => {
+                Self::ExplicitRxIndicator(InnerData::read(stream, max_size)?)
+            }
+            IoDataSampleRxIndicator::API_TYPE => {
+                Self::IoDataSampleRxIndicator(InnerData::read(stream, max_size)?)
+            }
+            SensorReadIndicator::API_TYPE => {
+                Self::SensorReadIndicator(InnerData::read(stream, max_size)?)
+            }
+            NodeIdentificationIndicator::API_TYPE => {
+                Self::NodeIdentificationIndicator(InnerData::read(stream, max_size)?)
+            }
+            x if x == ExtendedModemStatus::<N>::API_TYPE => {
+                Self::ExtendedModemStatus(InnerData::read(stream, max_size)?)
+            }
+            #[cfg(feature = "zigbee")]
+            OtaFirmwareUpdateStatus::API_TYPE =>
+            {
+                #[cfg(feature = "zigbee")]
+                Self::OtaFirmwareUpdateStatus(InnerData::read(stream, max_size)?)
+            }
+            #[cfg(feature = "zigbee")]
+            RouteRecordIndicator::API_TYPE =>
+            {
+                #[cfg(feature = "zigbee")]
+                Self::RouteRecordIndicator(InnerData::read(stream, max_size)?)
+            }
+            #[cfg(feature = "zigbee")]
+            DeviceAuthenticatedIndicator::API_TYPE =>
+            {
+                #[cfg(feature = "zigbee")]
+                Self::DeviceAuthenticatedIndicator(InnerData::read(stream, max_size)?)
+            }
+            #[cfg(feature = "zigbee")]
+            ManyToOneRouteRequestIndicator::API_TYPE =>
+            {
+                #[cfg(feature = "zigbee")]
+                Self::ManyToOneRouteRequestIndicator(InnerData::read(stream, max_size)?)
+            }
+            #[cfg(feature = "zigbee")]
+            RegisterJoiningDeviceStatus::API_TYPE =>
+            {
+                #[cfg(feature = "zigbee")]
+                Self::RegisterJoiningDeviceStatus(InnerData::read(stream, max_size)?)
+            }
+            api_type => Self::Unknown {
+                api_type,
+                raw: InnerData::read(stream, max_size)?,
+            },
+        })
+    }
+}
+
+impl<'a, const N: usize> TryFrom<RawFrame<'a>> for ReceivedFrame<N> {
+    type Error = DecodeError;
+
+    fn try_from(mut frame: RawFrame<'a>) -> Result<Self, DecodeError> {
+        let max_size = frame.payload.size();
+        Self::read(frame.api_type, &mut frame.payload, max_size)
+    }
+}
+
+/// Incremental API frame decoder, fed one byte at a time from the serial line.
+///
+/// `N` bounds the size of a whole frame including the delimiter, length and checksum.
+/// Frames with a bad checksum or that don't fit are dropped and the decoder resynchronizes
+/// on the next start delimiter. Without escaping that includes one that was buffered as part
+/// of the dropped frame, with escaping only a raw delimiter can start a frame.
+#[derive(Debug, Clone)]
+pub struct FrameDecoder<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    emitted: usize,
+    mode: ApiMode,
+    escaped: bool,
+}
+
+impl<const N: usize> Default for FrameDecoder<N> {
+    fn default() -> Self {
+        Self::new(ApiMode::default())
+    }
+}
+
+impl<const N: usize> FrameDecoder<N> {
+    pub const fn new(mode: ApiMode) -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+            emitted: 0,
+            mode,
+            escaped: false,
+        }
+    }
+
+    pub fn mode(&self) -> ApiMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: ApiMode) {
+        self.mode = mode;
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.emitted = 0;
+        self.escaped = false;
+    }
+
+    pub fn push(&mut self, byte: u8) -> Option<RawFrame<'_>> {
+        self.consume(self.emitted);
+        self.emitted = 0;
+
+        if self.len == N {
+            // Can't be a frame that fits, skip to the next delimiter.
+            self.resync();
+        }
+
+        let byte = match self.mode {
+            ApiMode::Unescaped => byte,
+            ApiMode::Escaped if byte == START_DELIMITER => {
+                self.reset();
+                byte
+            }
+            ApiMode::Escaped if self.len == 0 => return None,
+            ApiMode::Escaped if byte == ESCAPE => {
+                self.escaped = true;
+                return None;
+            }
+            ApiMode::Escaped if self.escaped => {
+                self.escaped = false;
+                byte ^ 0x20
+            }
+            ApiMode::Escaped => byte,
+        };
+        self.buf[self.len] = byte;
+        self.len += 1;
+
+        let frame_len = self.scan()?;
+        self.emitted = frame_len;
+        Some(RawFrame {
+            api_type: self.buf[3],
+            payload: stream::SliceReader::new(&self.buf[4..frame_len - 1], Endianness::BigEndian),
+        })
+    }
+
+    /// Returns the length of the complete frame at the start of the buffer, if there is one.
+    fn scan(&mut self) -> Option<usize> {
+        loop {
+            match self.buf[..self.len]
+                .iter()
+                .position(|&b| b == START_DELIMITER)
+            {
+                Some(start) => self.consume(start),
+                None => {
+                    self.len = 0;
+                    return None;
+                }
+            }
+            if self.len < 3 {
+                return None;
+            }
+
+            let data_len = u16::from_be_bytes([self.buf[1], self.buf[2]]) as usize;
+            let frame_len = data_len + 4;
+            if data_len == 0 || N < frame_len {
+                self.resync();
+                continue;
+            }
+            if self.len < frame_len {
+                return None;
+            }
+
+            let checksum = self.buf[3..frame_len]
+                .iter()
+                .fold(0u8, |sum, &b| sum.wrapping_add(b));
+            if checksum == 0xff {
+                return Some(frame_len);
+            }
+            self.resync();
+        }
+    }
+
+    fn resync(&mut self) {
+        match self.mode {
+            ApiMode::Unescaped => self.consume(1),
+            ApiMode::Escaped => {
+                self.len = 0;
+                self.escaped = false;
+            }
+        }
+    }
+
+    fn consume(&mut self, n: usize) {
+        self.buf.copy_within(n..self.len, 0);
+        self.len -= n;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::Vec;
+
+    /// ID, payload and checksum (0x13) all need escaping in API mode 2.
+    const FRAME: Frame<LocalAtCommandRequest<[u8; 4]>> = Frame(LocalAtCommandRequest {
+        id: 0x7e,
+        at_command: *b"NI",
+        parameter: [0x7d, 0x11, 0x13, 0x2e],
+    });
+    const PAYLOAD: [u8; 7] = [0x7e, b'N', b'I', 0x7d, 0x11, 0x13, 0x2e];
+
+    fn encode(mode: ApiMode) -> Vec<u8, 32> {
+        let mut out = Vec::new();
+        FRAME.write_mode(mode, &mut |bytes: &[u8]| {
+            out.extend_from_slice(bytes).unwrap()
+        });
+        out
+    }
+
+    fn decode(mode: ApiMode, bytes: &[u8]) -> Vec<(u8, Vec<u8, 32>), 4> {
+        let mut decoder = FrameDecoder::<32>::new(mode);
+        let mut frames = Vec::new();
+        for &byte in bytes {
+            if let Some(frame) = decoder.push(byte) {
+                let payload = Vec::from_slice(frame.payload.remaining_slice()).unwrap();
+                frames.push((frame.api_type, payload)).unwrap();
+            }
+        }
+        frames
+    }
+
+    fn expected() -> Vec<(u8, Vec<u8, 32>), 4> {
+        let mut frames = Vec::new();
+        frames
+            .push((0x08, Vec::from_slice(&PAYLOAD).unwrap()))
+            .unwrap();
+        frames
+    }
+
+    #[test]
+    fn escaped_round_trip() {
+        let encoded = encode(ApiMode::Escaped);
+        assert_eq!(
+            encoded[..],
+            [
+                0x7e, 0x00, 0x08, 0x08, 0x7d, 0x5e, b'N', b'I', 0x7d, 0x5d, 0x7d, 0x31, 0x7d, 0x33,
+                0x2e, 0x7d, 0x33,
+            ]
+        );
+        assert_eq!(decode(ApiMode::Escaped, &encoded), expected());
+    }
+
+    #[test]
+    fn unescaped_round_trip() {
+        let encoded = encode(ApiMode::Unescaped);
+        assert_eq!(encoded.len(), 12);
+        assert_eq!(decode(ApiMode::Unescaped, &encoded), expected());
+    }
+
+    #[test]
+    fn resyncs_after_garbage() {
+        for mode in [ApiMode::Unescaped, ApiMode::Escaped] {
+            let mut bytes: Vec<u8, 64> = Vec::from_slice(&[0x00, 0xff, 0x7d, 0x13, 0x42]).unwrap();
+            bytes.extend_from_slice(&encode(mode)).unwrap();
+            assert_eq!(decode(mode, &bytes), expected(), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn resyncs_after_truncated_frame() {
+        for mode in [ApiMode::Unescaped, ApiMode::Escaped] {
+            let frame = encode(mode);
+            let mut bytes: Vec<u8, 64> = Vec::from_slice(&frame[..frame.len() - 3]).unwrap();
+            bytes.extend_from_slice(&frame).unwrap();
+            assert_eq!(decode(mode, &bytes), expected(), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn drops_bad_checksum() {
+        for mode in [ApiMode::Unescaped, ApiMode::Escaped] {
+            let frame = encode(mode);
+            let mut bytes: Vec<u8, 64> = Vec::from_slice(&frame).unwrap();
+            *bytes.last_mut().unwrap() ^= 0x01;
+            assert!(decode(mode, &bytes).is_empty(), "{mode:?}");
+            bytes.extend_from_slice(&frame).unwrap();
+            assert_eq!(decode(mode, &bytes), expected(), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn io_data_sample() {
+        // The example from the XBee Zigbee manual: DIO2..4 and AD1 sampled.
+        let bytes = [
+            0x7e, 0x00, 0x14, 0x92, 0x00, 0x13, 0xa2, 0x00, 0x40, 0x52, 0x2b, 0xaa, 0x7d, 0x84,
+            0x01, 0x01, 0x00, 0x1c, 0x02, 0x00, 0x14, 0x02, 0x25, 0xf5,
+        ];
+        let mut decoder = FrameDecoder::<64>::new(ApiMode::Unescaped);
+        let frame = bytes.iter().find_map(|&byte| {
+            let frame = decoder.push(byte)?;
+            Some(ReceivedFrame::<64>::try_from(frame).unwrap())
+        });
+        let Some(ReceivedFrame::IoDataSampleRxIndicator(indicator)) = frame else {
+            panic!("expected an IO data sample, got {frame:?}");
+        };
+
+        assert_eq!(indicator.source_network_address, NetworkAddress(0x7d84));
+        let sample = &indicator.sample;
+        assert_eq!(sample.digital(2), Some(true));
+        assert_eq!(sample.digital(3), Some(false));
+        assert_eq!(sample.digital(4), Some(true));
+        assert_eq!(sample.digital(5), None);
+        assert_eq!(sample.analog(1), Some(0x225));
+        assert_eq!(sample.analog(0), None);
+        assert_eq!(sample.analog_millivolts(1, 1200), Some(644));
+
+        let mut written: Vec<u8, 32> = Vec::new();
+        Frame(indicator).write(&mut |b: &[u8]| written.extend_from_slice(b).unwrap());
+        assert_eq!(written[..], bytes);
+    }
+
+    #[cfg(feature = "zigbee")]
+    #[test]
+    fn install_code() {
+        // The example install code from the Zigbee specification.
+        let code = [
+            0x83, 0xfe, 0xd3, 0x40, 0x7a, 0x93, 0x97, 0x23, 0xa5, 0xc6, 0x39, 0xb2, 0x69, 0x16,
+            0xd5, 0x05,
+        ];
+        assert_eq!(install_code_crc(&code).to_le_bytes(), [0xc3, 0xb5]);
+
+        let ieee_address = IeeeAddress(0x0013a200_40401122);
+        let register = RegisterJoiningDevice::with_install_code(1, ieee_address, &code).unwrap();
+        assert_eq!(register.register_opts, RegisterOpts::INSTALL_CODE);
+        assert_eq!(register.key[..16], code);
+        assert_eq!(register.key[16..], [0xc3, 0xb5]);
+
+        assert_eq!(
+            RegisterJoiningDevice::with_install_code(1, ieee_address, &code[..7]),
+            None
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::frames::ReceivedFrame;
+use crate::{Error, PendingRequest, Xbee};
+use embedded_io::{ErrorType, Read, ReadReady, Write};
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Adapts a `std::io` serial port, e.g. a tty or a pseudo-terminal, to the `embedded_io`
+/// traits the [`Xbee`] driver runs on.
+///
+/// The port should have a read timeout (or be non-blocking). [`ReadReady`] is answered by
+/// trying a read, and one that times out or would block means nothing is ready.
+#[derive(Debug)]
+pub struct SerialPort<P> {
+    port: P,
+    read_buf: [u8; 64],
+    read_start: usize,
+    read_end: usize,
+}
+
+impl<P> SerialPort<P> {
+    pub fn new(port: P) -> Self {
+        Self {
+            port,
+            read_buf: [0; 64],
+            read_start: 0,
+            read_end: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &P {
+        &self.port
+    }
+
+    pub fn get_mut(&mut self) -> &mut P {
+        &mut self.port
+    }
+
+    pub fn into_inner(self) -> P {
+        self.port
+    }
+}
+
+impl<P> ErrorType for SerialPort<P> {
+    type Error = io::Error;
+}
+
+impl<P: io::Read> Read for SerialPort<P> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
+        if self.read_start == self.read_end {
+            return self.port.read(buf);
+        }
+        let len = buf.len().min(self.read_end - self.read_start);
+        buf[..len].copy_from_slice(&self.read_buf[self.read_start..self.read_start + len]);
+        self.read_start += len;
+        Ok(len)
+    }
+}
+
+impl<P: io::Read> ReadReady for SerialPort<P> {
+    fn read_ready(&mut self) -> Result<bool, io::Error> {
+        if self.read_start == self.read_end {
+            match self.port.read(&mut self.read_buf) {
+                Ok(len) => {
+                    self.read_start = 0;
+                    self.read_end = len;
+                }
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::WouldBlock
+                            | io::ErrorKind::TimedOut
+                            | io::ErrorKind::Interrupted
+                    ) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(self.read_start < self.read_end)
+    }
+}
+
+impl<P: io::Write> Write for SerialPort<P> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, io::Error> {
+        self.port.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), io::Error> {
+        self.port.flush()
+    }
+}
+
+impl<P: io::Read, const N: usize, const M: usize, const R: usize> Xbee<SerialPort<P>, N, M, R> {
+    const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+    /// Waits up to `timeout` for the next frame like [`Xbee::poll`], returning `None` if none
+    /// arrived in time.
+    #[allow(clippy::type_complexity)]
+    pub fn recv(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<(ReceivedFrame<N>, Option<PendingRequest>)>, Error<io::Error>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(received) = self.poll()? {
+                return Ok(Some(received));
+            }
+            if deadline <= Instant::now() {
+                return Ok(None);
+            }
+            thread::sleep(Self::POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::at::{self, AtCommand};
+    use crate::frames::{
+        ApiMode, AtCommandStatus, AtResponseStatus, Frame, LocalAtCommandResponse,
+    };
+    use std::collections::VecDeque;
+    use std::vec::Vec;
+
+    /// The radio end of the serial line, reads would block while it has nothing to send.
+    #[derive(Default)]
+    struct Radio {
+        to_host: VecDeque<u8>,
+        from_host: Vec<u8>,
+    }
+
+    impl io::Read for Radio {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.to_host.is_empty() {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            let len = buf.len().min(self.to_host.len());
+            for (dst, src) in buf.iter_mut().zip(self.to_host.drain(..len)) {
+                *dst = src;
+            }
+            Ok(len)
+        }
+    }
+
+    impl io::Write for Radio {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.from_host.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn request_and_response() {
+        let mut xbee = Xbee::<_, 64>::new(SerialPort::new(Radio::default()), ApiMode::Unescaped);
+        let id = xbee
+            .send_request(0, |id| at::PanId::query().with_id(id))
+            .unwrap();
+        let radio = xbee.serial().get_mut();
+        assert_eq!(
+            radio.from_host[..],
+            [0x7e, 0x00, 0x04, 0x08, id, b'I', b'D', 0x69]
+        );
+
+        let response = LocalAtCommandResponse {
+            id,
+            at_command: at::PanId::COMMAND,
+            command_status: AtResponseStatus {
+                status: AtCommandStatus::OK,
+                encrypted: false,
+            },
+            command_data: 0x1234u64,
+        };
+        Frame(response).write(&mut |bytes: &[u8]| radio.to_host.extend(bytes));
+
+        let (frame, request) = xbee.recv(Duration::from_millis(100)).unwrap().unwrap();
+        assert!(matches!(frame, ReceivedFrame::LocalAtCommandResponse(r) if r.id == id));
+        assert_eq!(request.map(|req| req.id), Some(id));
+        assert!(!xbee.frame_ids().is_pending(id));
+
+        assert_eq!(xbee.recv(Duration::from_millis(5)).unwrap(), None);
+    }
+}
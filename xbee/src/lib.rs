@@ -1,5 +1,13 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+// Links the Cortex-M runtime into firmware builds: the vector table, reset handler and the
+// `link.x` script that `.cargo/config.toml` passes to the linker.
+#[cfg(feature = "cortex-m")]
+extern crate cortex_m_rt;
+
 #[allow(unused_imports)]
 #[macro_use]
 extern crate xbee_derive;
@@ -7,12 +15,23 @@ extern crate xbee_derive;
 // use bitflags::bitflags;
 // use core::ops;
 
+#[cfg(feature = "async")]
+pub mod asynch;
+pub mod at;
+mod driver;
 pub mod frames;
+#[cfg(feature = "std")]
+pub mod host;
 pub mod stream;
+#[cfg(feature = "zigbee")]
 pub mod zdo;
+#[cfg(feature = "zigbee")]
 pub mod zha;
 
-pub use stream::{Endianness, InnerData, ReadStream, WriteStream};
+pub use driver::{Error, FrameIds, PendingRequest, Xbee};
+#[cfg(feature = "zigbee")]
+pub use driver::{SourceRoute, SourceRoutes};
+pub use stream::{DecodeError, Endianness, InnerData, ReadStream, WriteStream};
 
 pub trait Cluster {
     const PROFILE_ID: ProfileId;
@@ -42,6 +61,7 @@ pub struct ProfileId(pub u16);
 impl IeeeAddress {
     pub const COORDINATOR: Self = Self(0);
     pub const UNKNOWN: Self = Self(!0);
+    pub const BROADCAST: Self = Self(0xffff);
 }
 
 impl NetworkAddress {
@@ -51,6 +71,9 @@ impl NetworkAddress {
 
 impl ProfileId {
     pub const ZIGBEE_DEVICE: Self = Self(0);
+    pub const HOME_AUTOMATION: Self = Self(0x0104);
+    pub const SMART_ENERGY: Self = Self(0x0109);
+    pub const LIGHT_LINK: Self = Self(0xc05e);
 }
 
 impl ClusterId {}
@@ -58,8 +81,3 @@ impl ClusterId {}
 impl Endpoint {
     pub const ZIGBEE_DEVICE_OBJECT: Self = Self(0);
 }
-
-#[repr(C)]
-pub struct Xbee;
-
-impl Xbee {}
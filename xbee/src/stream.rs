@@ -1,4 +1,4 @@
-use core::{array, marker::PhantomData, ops, slice};
+use core::{marker::PhantomData, ops, slice};
 use heapless::{String, Vec};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -10,6 +10,31 @@ pub enum Endianness {
 // 0x04030201 32bit => LE: 1 2 3 4
 //                     BE: 4 3 2 1
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// The stream or `max_size` ended before the value did.
+    Truncated,
+    /// A discriminant that isn't one of the enum's variants.
+    InvalidEnumValue,
+    InvalidUtf8,
+    /// The value has more elements than its container can hold.
+    CapacityOverflow,
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Truncated => "data ended before the value did",
+            Self::InvalidEnumValue => "invalid enum value",
+            Self::InvalidUtf8 => "invalid UTF-8 string",
+            Self::CapacityOverflow => "value too large for its container",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
 pub trait WriteStream {
     fn endianness(&self) -> Endianness;
     fn write(&mut self, bytes: &[u8]);
@@ -18,7 +43,7 @@ pub trait WriteStream {
 pub trait ReadStream {
     fn endianness(&self) -> Endianness;
     fn size(&self) -> usize;
-    fn read(&mut self, bytes: &mut [u8]);
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError>;
 }
 
 pub trait InnerData: Sized {
@@ -27,10 +52,10 @@ pub trait InnerData: Sized {
 
     fn byte_size(&self) -> usize;
     fn write<T: WriteStream>(&self, stream: &mut T);
-    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Self;
+    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Result<Self, DecodeError>;
 }
 
-impl<'a, T: ReadStream> ReadStream for &'a mut T {
+impl<T: ReadStream> ReadStream for &mut T {
     #[inline]
     fn endianness(&self) -> Endianness {
         T::endianness(self)
@@ -40,12 +65,12 @@ impl<'a, T: ReadStream> ReadStream for &'a mut T {
         T::size(self)
     }
     #[inline]
-    fn read(&mut self, bytes: &mut [u8]) {
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
         T::read(self, bytes)
     }
 }
 
-impl<'a, T: WriteStream> WriteStream for &'a mut T {
+impl<T: WriteStream> WriteStream for &mut T {
     #[inline]
     fn endianness(&self) -> Endianness {
         T::endianness(self)
@@ -56,9 +81,180 @@ impl<'a, T: WriteStream> WriteStream for &'a mut T {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OverflowError;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SliceReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    endianness: Endianness,
+}
+
+impl<'a> SliceReader<'a> {
+    pub fn new(bytes: &'a [u8], endianness: Endianness) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            endianness,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    pub fn remaining_slice(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+}
+
+impl<'a> ReadStream for SliceReader<'a> {
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+    fn size(&self) -> usize {
+        self.remaining()
+    }
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let src = self
+            .remaining_slice()
+            .get(..bytes.len())
+            .ok_or(DecodeError::Truncated)?;
+        bytes.copy_from_slice(src);
+        self.pos += bytes.len();
+        Ok(())
+    }
+}
+
+/// A fixed capacity byte buffer a [`SliceWriter`] can write into.
+pub trait WriteBuffer {
+    fn capacity(&self) -> usize;
+    /// The length of the data already in the buffer, which a new writer appends to.
+    fn filled(&self) -> usize;
+    /// Writes `bytes` at `pos`, which is at most the current end of the written data.
+    fn put(&mut self, pos: usize, bytes: &[u8]);
+    fn as_slice(&self) -> &[u8];
+}
+
+impl WriteBuffer for &mut [u8] {
+    fn capacity(&self) -> usize {
+        self.len()
+    }
+    fn filled(&self) -> usize {
+        0
+    }
+    fn put(&mut self, pos: usize, bytes: &[u8]) {
+        self[pos..pos + bytes.len()].copy_from_slice(bytes);
+    }
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<const N: usize> WriteBuffer for Vec<u8, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn filled(&self) -> usize {
+        self.len()
+    }
+    fn put(&mut self, pos: usize, bytes: &[u8]) {
+        self.truncate(pos);
+        self.extend_from_slice(bytes).ok();
+    }
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<const N: usize> WriteBuffer for &mut Vec<u8, N> {
+    fn capacity(&self) -> usize {
+        N
+    }
+    fn filled(&self) -> usize {
+        self.len()
+    }
+    fn put(&mut self, pos: usize, bytes: &[u8]) {
+        (**self).put(pos, bytes)
+    }
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+}
+
+/// Writes into a [`WriteBuffer`], stopping at its capacity.
+///
+/// Writes that don't fit are dropped whole and flag the writer as overflowed, which
+/// [`SliceWriter::finish`] reports.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SliceWriter<B> {
+    buf: B,
+    pos: usize,
+    endianness: Endianness,
+    overflowed: bool,
+}
+
+impl<B: WriteBuffer> SliceWriter<B> {
+    pub fn new(buf: B, endianness: Endianness) -> Self {
+        Self {
+            pos: buf.filled(),
+            buf,
+            endianness,
+            overflowed: false,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.capacity() - self.pos
+    }
+
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    pub fn written(&self) -> &[u8] {
+        &self.buf.as_slice()[..self.pos]
+    }
+
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+
+    /// Returns the number of bytes written, or an error if any write didn't fit.
+    pub fn finish(self) -> Result<usize, OverflowError> {
+        match self.overflowed {
+            false => Ok(self.pos),
+            true => Err(OverflowError),
+        }
+    }
+}
+
+impl<B: WriteBuffer> WriteStream for SliceWriter<B> {
+    fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        if self.remaining() < bytes.len() {
+            self.overflowed = true;
+            return;
+        }
+        self.buf.put(self.pos, bytes);
+        self.pos += bytes.len();
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
-pub struct OverwriteLittleEndian<T>(T);
+pub struct OverwriteLittleEndian<T>(pub T);
 
 impl<T> ops::Deref for OverwriteLittleEndian<T> {
     type Target = T;
@@ -80,8 +276,8 @@ impl<T: ReadStream> ReadStream for OverwriteLittleEndian<T> {
     fn size(&self) -> usize {
         self.0.size()
     }
-    fn read(&mut self, bytes: &mut [u8]) {
-        self.0.read(bytes);
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.0.read(bytes)
     }
 }
 
@@ -101,8 +297,8 @@ impl<T: InnerData> InnerData for OverwriteLittleEndian<T> {
     fn byte_size(&self) -> usize {
         self.0.byte_size()
     }
-    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Self {
-        Self(T::read(&mut OverwriteLittleEndian(stream), max_size))
+    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+        Ok(Self(T::read(&mut OverwriteLittleEndian(stream), max_size)?))
     }
     fn write<S: WriteStream>(&self, stream: &mut S) {
         T::write(self, &mut OverwriteLittleEndian(stream));
@@ -111,7 +307,7 @@ impl<T: InnerData> InnerData for OverwriteLittleEndian<T> {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
-pub struct OverwriteBigEndian<T>(T);
+pub struct OverwriteBigEndian<T>(pub T);
 
 impl<T> ops::Deref for OverwriteBigEndian<T> {
     type Target = T;
@@ -133,8 +329,8 @@ impl<T: ReadStream> ReadStream for OverwriteBigEndian<T> {
     fn size(&self) -> usize {
         self.0.size()
     }
-    fn read(&mut self, bytes: &mut [u8]) {
-        self.0.read(bytes);
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.0.read(bytes)
     }
 }
 
@@ -154,8 +350,8 @@ impl<T: InnerData> InnerData for OverwriteBigEndian<T> {
     fn byte_size(&self) -> usize {
         self.0.byte_size()
     }
-    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Self {
-        Self(T::read(&mut OverwriteBigEndian(stream), max_size))
+    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+        Ok(Self(T::read(&mut OverwriteBigEndian(stream), max_size)?))
     }
     fn write<S: WriteStream>(&self, stream: &mut S) {
         T::write(self, &mut OverwriteBigEndian(stream));
@@ -178,17 +374,16 @@ macro_rules! impl_writable {
                 };
                 stream.write(&bytes);
             }
-            fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Self {
-                assert!(
-                    Self::MIN_SIZE <= max_size,
-                    "Called `InnerData::read` with `max_size` that is less than the minimum `InnerData::MIN_SIZE`",
-                );
+            fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Result<Self, DecodeError> {
+                if max_size < Self::MIN_SIZE {
+                    return Err(DecodeError::Truncated);
+                }
                 let mut bytes = [0; Self::MIN_SIZE];
-                stream.read(&mut bytes);
-                match stream.endianness() {
+                stream.read(&mut bytes)?;
+                Ok(match stream.endianness() {
                     Endianness::LittleEndian => Self::from_le_bytes(bytes),
                     Endianness::BigEndian => Self::from_be_bytes(bytes),
-                }
+                })
             }
         }
     };
@@ -218,14 +413,26 @@ impl InnerData for bool {
     fn write<T: WriteStream>(&self, stream: &mut T) {
         stream.write(&[*self as u8])
     }
-    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Self {
-        assert!(
-            0 < max_size,
-            "Called `InnerData::read` with `max_size` that is less than the minimum `InnerData::MIN_SIZE`",
-        );
+    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Result<Self, DecodeError> {
+        if max_size < Self::MIN_SIZE {
+            return Err(DecodeError::Truncated);
+        }
         let mut byte = 0;
-        stream.read(slice::from_mut(&mut byte));
-        byte != 0
+        stream.read(slice::from_mut(&mut byte))?;
+        Ok(byte != 0)
+    }
+}
+
+impl InnerData for () {
+    const MAX_SIZE: Option<usize> = Some(0);
+    const MIN_SIZE: usize = 0;
+
+    fn byte_size(&self) -> usize {
+        0
+    }
+    fn write<T: WriteStream>(&self, _stream: &mut T) {}
+    fn read<T: ReadStream>(_stream: &mut T, _max_size: usize) -> Result<Self, DecodeError> {
+        Ok(())
     }
 }
 
@@ -244,19 +451,20 @@ impl<T: InnerData, const N: usize> InnerData for [T; N] {
             x.write(stream);
         }
     }
-    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Self {
-        assert!(
-            Self::MIN_SIZE < max_size,
-            "Called `InnerData::read` with `max_size` that is less than the minimum `InnerData::MIN_SIZE`",
-        );
-        let mut field_size =
-            Self::MAX_SIZE.map_or(max_size, |c_max_size| c_max_size.min(max_size)) - Self::MIN_SIZE;
-        array::from_fn::<T, N, _>(|_| {
+    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+        let mut field_size = Self::MAX_SIZE
+            .map_or(max_size, |c_max_size| c_max_size.min(max_size))
+            .checked_sub(Self::MIN_SIZE)
+            .ok_or(DecodeError::Truncated)?;
+
+        let mut vec = Vec::<T, N>::new();
+        for _ in 0..N {
             field_size += T::MIN_SIZE;
-            let value = T::read(stream, field_size);
+            let value = T::read(stream, field_size)?;
             field_size -= value.byte_size();
-            value
-        })
+            vec.push(value).ok();
+        }
+        Ok(vec.into_array().unwrap_or_else(|_| unreachable!()))
     }
 }
 
@@ -271,9 +479,9 @@ macro_rules! num_len {
                 Self(len)
             }
         }
-        impl Into<$int> for $name {
-            fn into(self) -> $int {
-                self.0
+        impl From<$name> for $int {
+            fn from(len: $name) -> Self {
+                len.0
             }
         }
         impl From<usize> for $name {
@@ -281,9 +489,9 @@ macro_rules! num_len {
                 Self(len as _)
             }
         }
-        impl Into<usize> for $name {
-            fn into(self) -> usize {
-                self.0 as _
+        impl From<$name> for usize {
+            fn from(len: $name) -> Self {
+                len.0 as _
             }
         }
     };
@@ -294,9 +502,27 @@ num_len!(U16Len(u16));
 num_len!(U32Len(u32));
 num_len!(U64Len(u64));
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A vec without a length prefix that takes up the rest of `max_size` (or of the stream),
+/// so it can only be the last field of a frame.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct HungryVec<T, const N: usize>(Vec<T, N>);
 
+impl<T, const N: usize> HungryVec<T, N> {
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn into_inner(self) -> Vec<T, N> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> From<Vec<T, N>> for HungryVec<T, N> {
+    fn from(vec: Vec<T, N>) -> Self {
+        Self(vec)
+    }
+}
+
 impl<T, const N: usize> ops::Deref for HungryVec<T, N> {
     type Target = Vec<T, N>;
     fn deref(&self) -> &Vec<T, N> {
@@ -311,35 +537,202 @@ impl<T, const N: usize> ops::DerefMut for HungryVec<T, N> {
 }
 
 impl<T: InnerData, const N: usize> InnerData for HungryVec<T, N> {
-    const MAX_SIZE: Option<usize> = Some(N);
+    const MAX_SIZE: Option<usize> = match T::MAX_SIZE {
+        Some(max_size) => Some(max_size * N),
+        None => None,
+    };
     const MIN_SIZE: usize = 0;
 
     fn byte_size(&self) -> usize {
         self.iter().map(InnerData::byte_size).sum::<usize>()
     }
 
-    fn read<S: ReadStream>(stream: &mut S, mut max_size: usize) -> Self {
-        let mut size_remaning = max_size;
+    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+        let mut size_remaining = max_size.min(stream.size());
 
         let mut vec = Vec::new();
+        while 0 < size_remaining {
+            let value = T::read(stream, size_remaining)?;
+            let size = value.byte_size();
+            vec.push(value).map_err(|_| DecodeError::CapacityOverflow)?;
+            if size == 0 {
+                break;
+            }
+            size_remaining -= size;
+        }
 
-        Self(vec)
+        Ok(Self(vec))
     }
 
     fn write<S: WriteStream>(&self, stream: &mut S) {
-        U::from(self.len()).write(stream);
         for x in &**self {
             x.write(stream);
         }
     }
 }
 
+/// A string without a length prefix that takes up the rest of `max_size` (or of the stream),
+/// so it can only be the last field of a frame.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct HungryString<const N: usize>(String<N>);
+
+impl<const N: usize> HungryString<N> {
+    pub const fn new() -> Self {
+        Self(String::new())
+    }
+
+    pub fn into_inner(self) -> String<N> {
+        self.0
+    }
+}
+
+impl<const N: usize> From<String<N>> for HungryString<N> {
+    fn from(string: String<N>) -> Self {
+        Self(string)
+    }
+}
+
+impl<const N: usize> ops::Deref for HungryString<N> {
+    type Target = String<N>;
+    fn deref(&self) -> &String<N> {
+        &self.0
+    }
+}
+
+impl<const N: usize> ops::DerefMut for HungryString<N> {
+    fn deref_mut(&mut self) -> &mut String<N> {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> InnerData for HungryString<N> {
+    const MAX_SIZE: Option<usize> = Some(N);
+    const MIN_SIZE: usize = 0;
+
+    fn byte_size(&self) -> usize {
+        self.len()
+    }
+
+    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+        let len = max_size.min(stream.size());
+        if N < len {
+            return Err(DecodeError::CapacityOverflow);
+        }
+
+        let bytes = &mut [0u8; N][..len];
+        stream.read(bytes)?;
+        Ok(Self(
+            core::str::from_utf8(bytes)
+                .map_err(|_| DecodeError::InvalidUtf8)?
+                .into(),
+        ))
+    }
+
+    fn write<S: WriteStream>(&self, stream: &mut S) {
+        stream.write(self.as_bytes());
+    }
+}
+
+/// A string ended by a zero byte rather than prefixed by its length.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct NulString<const N: usize>(String<N>);
+
+impl<const N: usize> NulString<N> {
+    pub const fn new() -> Self {
+        Self(String::new())
+    }
+
+    pub fn into_inner(self) -> String<N> {
+        self.0
+    }
+}
+
+impl<const N: usize> From<String<N>> for NulString<N> {
+    fn from(string: String<N>) -> Self {
+        Self(string)
+    }
+}
+
+impl<const N: usize> ops::Deref for NulString<N> {
+    type Target = String<N>;
+    fn deref(&self) -> &String<N> {
+        &self.0
+    }
+}
+
+impl<const N: usize> ops::DerefMut for NulString<N> {
+    fn deref_mut(&mut self) -> &mut String<N> {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> InnerData for NulString<N> {
+    const MAX_SIZE: Option<usize> = Some(N + 1);
+    const MIN_SIZE: usize = 1;
+
+    fn byte_size(&self) -> usize {
+        self.len() + 1
+    }
+
+    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+        let mut bytes = Vec::<u8, N>::new();
+        for _ in 0..max_size {
+            match u8::read(stream, 1)? {
+                0 => {
+                    return Ok(Self(
+                        core::str::from_utf8(&bytes)
+                            .map_err(|_| DecodeError::InvalidUtf8)?
+                            .into(),
+                    ))
+                }
+                byte => bytes
+                    .push(byte)
+                    .map_err(|_| DecodeError::CapacityOverflow)?,
+            }
+        }
+        Err(DecodeError::Truncated)
+    }
+
+    fn write<S: WriteStream>(&self, stream: &mut S) {
+        stream.write(self.as_bytes());
+        stream.write(&[0]);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SizeVec<U, T, const N: usize> {
     vec: Vec<T, N>,
     _phantom: PhantomData<U>,
 }
 
+impl<U, T, const N: usize> SizeVec<U, T, N> {
+    pub const fn new() -> Self {
+        Self {
+            vec: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> Vec<T, N> {
+        self.vec
+    }
+}
+
+impl<U, T, const N: usize> Default for SizeVec<U, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U, T, const N: usize> From<Vec<T, N>> for SizeVec<U, T, N> {
+    fn from(vec: Vec<T, N>) -> Self {
+        Self {
+            vec,
+            _phantom: PhantomData,
+        }
+    }
+}
+
 impl<U, T, const N: usize> ops::Deref for SizeVec<U, T, N> {
     type Target = Vec<T, N>;
     fn deref(&self) -> &Vec<T, N> {
@@ -368,26 +761,30 @@ where
         U::from(self.len()).byte_size() + self.iter().map(InnerData::byte_size).sum::<usize>()
     }
 
-    fn read<S: ReadStream>(stream: &mut S, mut max_size: usize) -> Self {
-        let u_len = U::read(stream, max_size);
+    fn read<S: ReadStream>(stream: &mut S, mut max_size: usize) -> Result<Self, DecodeError> {
+        let u_len = U::read(stream, max_size)?;
         max_size -= u_len.byte_size();
 
         let len: usize = u_len.into();
+        if N < len {
+            return Err(DecodeError::CapacityOverflow);
+        }
         let mut field_size = T::MAX_SIZE
             .map_or(max_size, |c_max_size| (len * c_max_size).min(max_size))
-            - len * T::MIN_SIZE;
+            .checked_sub(len * T::MIN_SIZE)
+            .ok_or(DecodeError::Truncated)?;
 
-        Self {
-            vec: (0..len)
-                .map(|_| {
-                    field_size += T::MIN_SIZE;
-                    let value = T::read(stream, field_size);
-                    field_size -= value.byte_size();
-                    value
-                })
-                .collect(),
-            _phantom: PhantomData,
+        let mut vec = Vec::new();
+        for _ in 0..len {
+            field_size += T::MIN_SIZE;
+            let value = T::read(stream, field_size)?;
+            field_size -= value.byte_size();
+            vec.push(value).ok();
         }
+        Ok(Self {
+            vec,
+            _phantom: PhantomData,
+        })
     }
 
     fn write<S: WriteStream>(&self, stream: &mut S) {
@@ -406,18 +803,20 @@ impl<const N: usize> InnerData for String<N> {
         1 + self.len()
     }
 
-    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Self {
-        let len = u8::read(stream, 1) as usize;
-        assert!(
-            len < max_size,
-            "String length too long to fit in `max_size`"
-        );
+    fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Result<Self, DecodeError> {
+        let len = u8::read(stream, max_size)? as usize;
+        if N < len {
+            return Err(DecodeError::CapacityOverflow);
+        }
+        if max_size <= len {
+            return Err(DecodeError::Truncated);
+        }
 
         let bytes = &mut [0u8; N][..len];
-        stream.read(bytes);
-        core::str::from_utf8(&bytes)
-            .expect("Read a bad UTF-8 string")
-            .into()
+        stream.read(bytes)?;
+        Ok(core::str::from_utf8(bytes)
+            .map_err(|_| DecodeError::InvalidUtf8)?
+            .into())
     }
 
     fn write<S: WriteStream>(&self, stream: &mut S) {
@@ -457,10 +856,13 @@ macro_rules! inner_data_enum {
             fn write<S: WriteStream>(&self, stream: &mut S) {
                 <$ty as $crate::InnerData>::write(&(*self as $ty), stream);
             }
-            fn read<S: ReadStream>(stream: &mut S, max_size: usize) -> Self {
-                match <$ty as $crate::InnerData>::read(stream, max_size) {
-                    $($value => Self::$variant,)+
-                    x => panic!("Read an the unsupported value {x} for `{}`", stringify!($name)),
+            fn read<S: ReadStream>(
+                stream: &mut S,
+                max_size: usize,
+            ) -> Result<Self, $crate::DecodeError> {
+                match <$ty as $crate::InnerData>::read(stream, max_size)? {
+                    $($value => Ok(Self::$variant),)+
+                    _ => Err($crate::DecodeError::InvalidEnumValue),
                 }
             }
         }
@@ -468,3 +870,111 @@ macro_rules! inner_data_enum {
     };
 }
 pub use inner_data_enum;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read<T: InnerData>(bytes: &[u8], max_size: usize) -> Result<T, DecodeError> {
+        T::read(
+            &mut SliceReader::new(bytes, Endianness::BigEndian),
+            max_size,
+        )
+    }
+
+    #[derive(Debug, PartialEq, InnerData)]
+    struct Pair {
+        a: u16,
+        b: u32,
+    }
+
+    inner_data_enum! {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Color: u8 {
+            Red = 1,
+            Green = 2,
+        }
+    }
+
+    #[test]
+    fn truncated_struct() {
+        let bytes = [0x00, 0x01, 0x00, 0x02];
+        assert_eq!(
+            read::<Pair>(&bytes, bytes.len()),
+            Err(DecodeError::Truncated)
+        );
+        assert_eq!(
+            read::<Pair>(&bytes, usize::MAX),
+            Err(DecodeError::Truncated)
+        );
+        assert_eq!(
+            read::<Pair>(&[0x00, 0x01, 0x00, 0x00, 0x00, 0x02], 6),
+            Ok(Pair { a: 1, b: 2 })
+        );
+    }
+
+    #[test]
+    fn unknown_enum_value() {
+        assert_eq!(read::<Color>(&[2], 1), Ok(Color::Green));
+        assert_eq!(read::<Color>(&[3], 1), Err(DecodeError::InvalidEnumValue));
+    }
+
+    #[test]
+    fn invalid_utf8_string() {
+        let bytes = [2, 0xff, 0xfe];
+        assert_eq!(
+            read::<String<4>>(&bytes, bytes.len()),
+            Err(DecodeError::InvalidUtf8)
+        );
+    }
+
+    #[test]
+    fn size_vec_over_capacity() {
+        let bytes = [3, 1, 2, 3];
+        assert_eq!(
+            read::<SizeVec<U8Len, u8, 2>>(&bytes, bytes.len()),
+            Err(DecodeError::CapacityOverflow)
+        );
+    }
+
+    #[cfg(feature = "zigbee")]
+    #[test]
+    fn zdo_response_round_trip() {
+        use crate::zdo::{ActiveEndpointsResponse, StatusCode, ZdoFrame};
+        use crate::{Endpoint, NetworkAddress};
+
+        let response = ZdoFrame {
+            transaction_sequence: 7,
+            data: ActiveEndpointsResponse {
+                status: StatusCode(0),
+                network_address: NetworkAddress(0x1234),
+                active_endpoint_list: Vec::from_slice(&[Endpoint(0x01), Endpoint(0xe8)])
+                    .unwrap()
+                    .into(),
+            },
+        };
+
+        let mut writer = SliceWriter::new(Vec::<u8, 16>::new(), Endianness::LittleEndian);
+        response.write(&mut writer);
+        assert_eq!(writer.written(), [7, 0x00, 0x34, 0x12, 2, 0x01, 0xe8]);
+        let bytes = writer.into_inner();
+
+        let mut reader = SliceReader::new(&bytes, Endianness::LittleEndian);
+        assert_eq!(InnerData::read(&mut reader, bytes.len()), Ok(response));
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn slice_writer_overflow() {
+        let mut buf = [0u8; 3];
+        let mut writer = SliceWriter::new(&mut buf[..], Endianness::BigEndian);
+        0x0102u16.write(&mut writer);
+        0x0304u16.write(&mut writer);
+        assert_eq!(writer.written(), [0x01, 0x02]);
+        assert_eq!(writer.finish(), Err(OverflowError));
+
+        let mut writer = SliceWriter::new(&mut buf[..], Endianness::BigEndian);
+        0x0102u16.write(&mut writer);
+        assert_eq!(writer.finish(), Ok(2));
+    }
+}
@@ -1,4 +1,4 @@
-pub use crate::stream::{Endianness, InnerData, ReadStream, WriteStream};
+pub use crate::stream::{DecodeError, Endianness, InnerData, ReadStream, WriteStream};
 use crate::{stream, Cluster, ClusterId, Endpoint, IeeeAddress, NetworkAddress, ProfileId};
 use bitflags::bitflags;
 use heapless::Vec;
@@ -39,6 +39,18 @@ use heapless::Vec;
 #[repr(transparent)]
 pub struct StatusCode(pub u8);
 
+/// A ZDO command or response preceded by its transaction sequence number, as sent over the air.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
+pub struct ZdoFrame<T> {
+    pub transaction_sequence: u8,
+    pub data: T,
+}
+
+impl<T: Cluster> Cluster for ZdoFrame<T> {
+    const PROFILE_ID: ProfileId = T::PROFILE_ID;
+    const CLUSTER_ID: ClusterId = T::CLUSTER_ID;
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, InnerData)]
 pub struct NetworkAddressRequest {
     pub ieee_address: IeeeAddress,
@@ -86,29 +98,30 @@ impl InnerData for NetworkAddressResponseExtended {
             + self.start_index.byte_size()
             + self.addresses.len() * NetworkAddress::MIN_SIZE
     }
-    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Self {
-        assert!(Self::MIN_SIZE <= max_size, "`max_size` too small");
-        let status = StatusCode::read(stream, StatusCode::MIN_SIZE);
-        let ieee_address = IeeeAddress::read(stream, IeeeAddress::MIN_SIZE);
-        let network_address = NetworkAddress::read(stream, NetworkAddress::MIN_SIZE);
-        let num_addresses = u8::read(stream, 1);
-        let start_index = u8::read(stream, 1);
-
-        assert!(
-            Self::MIN_SIZE + num_addresses as usize * NetworkAddress::MIN_SIZE <= max_size,
-            "`max_size` too small for the read address count"
-        );
+    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Result<Self, DecodeError> {
+        if max_size < Self::MIN_SIZE {
+            return Err(DecodeError::Truncated);
+        }
+        let status = StatusCode::read(stream, StatusCode::MIN_SIZE)?;
+        let ieee_address = IeeeAddress::read(stream, IeeeAddress::MIN_SIZE)?;
+        let network_address = NetworkAddress::read(stream, NetworkAddress::MIN_SIZE)?;
+        let num_addresses = u8::read(stream, 1)?;
+        let start_index = u8::read(stream, 1)?;
+
+        if max_size < Self::MIN_SIZE + num_addresses as usize * NetworkAddress::MIN_SIZE {
+            return Err(DecodeError::Truncated);
+        }
         let addresses = (0..num_addresses)
             .map(|_| NetworkAddress::read(stream, NetworkAddress::MIN_SIZE))
-            .collect();
-        Self {
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
             status,
             ieee_address,
             network_address,
             num_addresses,
             start_index,
             addresses,
-        }
+        })
     }
     fn write<T: WriteStream>(&self, stream: &mut T) {
         self.status.write(stream);
@@ -123,6 +136,7 @@ impl InnerData for NetworkAddressResponseExtended {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(clippy::large_enum_variant)]
 pub enum NetworkAddressResponse {
     Single(NetworkAddressResponseSingle),
     Extended(NetworkAddressResponseExtended),
@@ -138,12 +152,12 @@ impl InnerData for NetworkAddressResponse {
             Self::Extended(resp) => resp.byte_size(),
         }
     }
-    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Self {
-        if max_size < NetworkAddressResponseExtended::MIN_SIZE {
-            Self::Single(NetworkAddressResponseSingle::read(stream, max_size))
+    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Result<Self, DecodeError> {
+        Ok(if max_size < NetworkAddressResponseExtended::MIN_SIZE {
+            Self::Single(NetworkAddressResponseSingle::read(stream, max_size)?)
         } else {
-            Self::Extended(NetworkAddressResponseExtended::read(stream, max_size))
-        }
+            Self::Extended(NetworkAddressResponseExtended::read(stream, max_size)?)
+        })
     }
     fn write<T: WriteStream>(&self, stream: &mut T) {
         match self {
@@ -205,29 +219,30 @@ impl InnerData for IeeeAddressResponseExtended {
             + self.start_index.byte_size()
             + self.addresses.len() * NetworkAddress::MIN_SIZE
     }
-    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Self {
-        assert!(Self::MIN_SIZE <= max_size, "`max_size` too small");
-        let status = StatusCode::read(stream, StatusCode::MIN_SIZE);
-        let ieee_address = IeeeAddress::read(stream, IeeeAddress::MIN_SIZE);
-        let network_address = NetworkAddress::read(stream, NetworkAddress::MIN_SIZE);
-        let num_addresses = u8::read(stream, 1);
-        let start_index = u8::read(stream, 1);
-
-        assert!(
-            Self::MIN_SIZE + num_addresses as usize * NetworkAddress::MIN_SIZE <= max_size,
-            "`max_size` too small for the read address count"
-        );
+    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Result<Self, DecodeError> {
+        if max_size < Self::MIN_SIZE {
+            return Err(DecodeError::Truncated);
+        }
+        let status = StatusCode::read(stream, StatusCode::MIN_SIZE)?;
+        let ieee_address = IeeeAddress::read(stream, IeeeAddress::MIN_SIZE)?;
+        let network_address = NetworkAddress::read(stream, NetworkAddress::MIN_SIZE)?;
+        let num_addresses = u8::read(stream, 1)?;
+        let start_index = u8::read(stream, 1)?;
+
+        if max_size < Self::MIN_SIZE + num_addresses as usize * NetworkAddress::MIN_SIZE {
+            return Err(DecodeError::Truncated);
+        }
         let addresses = (0..num_addresses)
             .map(|_| NetworkAddress::read(stream, NetworkAddress::MIN_SIZE))
-            .collect();
-        Self {
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
             status,
             ieee_address,
             network_address,
             num_addresses,
             start_index,
             addresses,
-        }
+        })
     }
     fn write<T: WriteStream>(&self, stream: &mut T) {
         self.status.write(stream);
@@ -242,6 +257,7 @@ impl InnerData for IeeeAddressResponseExtended {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[allow(clippy::large_enum_variant)]
 pub enum IeeeAddressResponse {
     Single(IeeeAddressResponseSingle),
     Extended(IeeeAddressResponseExtended),
@@ -257,12 +273,12 @@ impl InnerData for IeeeAddressResponse {
             Self::Extended(resp) => resp.byte_size(),
         }
     }
-    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Self {
-        if max_size < IeeeAddressResponseExtended::MIN_SIZE {
-            Self::Single(IeeeAddressResponseSingle::read(stream, max_size))
+    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Result<Self, DecodeError> {
+        Ok(if max_size < IeeeAddressResponseExtended::MIN_SIZE {
+            Self::Single(IeeeAddressResponseSingle::read(stream, max_size)?)
         } else {
-            Self::Extended(IeeeAddressResponseExtended::read(stream, max_size))
-        }
+            Self::Extended(IeeeAddressResponseExtended::read(stream, max_size)?)
+        })
     }
     fn write<T: WriteStream>(&self, stream: &mut T) {
         match self {
@@ -1,7 +1,5 @@
-pub use crate::stream::{Endianness, InnerData, ReadStream, WriteStream};
-use crate::{
-    inner_data_enum, stream, Cluster, ClusterId, Endpoint, IeeeAddress, NetworkAddress, ProfileId,
-};
+pub use crate::stream::{DecodeError, Endianness, InnerData, ReadStream, WriteStream};
+use crate::{inner_data_enum, stream};
 use bitflags::bitflags;
 use heapless::String;
 
@@ -41,7 +39,7 @@ inner_data_enum! {
         BatteryBackupedEmergencyMainsAndTransferSwitch = 0x86,
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
     pub enum PhysicalEnvironment: u8 {
         UnspecifiedEnvironment = 0x00,
         DeprecatedMirrorCapacityAvailable = 0x01,
@@ -153,10 +151,82 @@ inner_data_enum! {
         DecontaminationRoom = 0x6f,
         Atrium = 0x70,
         Mirror = 0x71,
+        #[default]
         UnknownEnvironment = 0xff,
     }
 }
 
+bitflags! {
+    #[derive(Default, InnerData)]
+    pub struct ZclFrameControl: u8 {
+        const CLUSTER_SPECIFIC = 1 << 0;
+        const MANUFACTURER_SPECIFIC = 1 << 2;
+        const SERVER_TO_CLIENT = 1 << 3;
+        const DISABLE_DEFAULT_RESPONSE = 1 << 4;
+    }
+}
+
+/// A ZCL frame, the manufacturer code is present iff `MANUFACTURER_SPECIFIC` is set.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZclFrame<const N: usize> {
+    pub frame_control: ZclFrameControl,
+    pub manufacturer_code: Option<u16>,
+    pub transaction_sequence: u8,
+    pub command_id: u8,
+    pub payload: stream::HungryVec<u8, N>,
+}
+
+impl<const N: usize> InnerData for ZclFrame<N> {
+    const MIN_SIZE: usize = ZclFrameControl::MIN_SIZE + u8::MIN_SIZE + u8::MIN_SIZE;
+    const MAX_SIZE: Option<usize> = match <stream::HungryVec<u8, N>>::MAX_SIZE {
+        Some(max_size) => Some(Self::MIN_SIZE + u16::MIN_SIZE + max_size),
+        None => None,
+    };
+
+    fn byte_size(&self) -> usize {
+        self.frame_control.byte_size()
+            + self.manufacturer_code.map_or(0, |code| code.byte_size())
+            + self.transaction_sequence.byte_size()
+            + self.command_id.byte_size()
+            + self.payload.byte_size()
+    }
+    fn read<T: ReadStream>(stream: &mut T, max_size: usize) -> Result<Self, DecodeError> {
+        if max_size < Self::MIN_SIZE {
+            return Err(DecodeError::Truncated);
+        }
+        let frame_control = ZclFrameControl::read(stream, ZclFrameControl::MIN_SIZE)?;
+        let mut max_size = max_size - Self::MIN_SIZE;
+        let manufacturer_code = match frame_control.contains(ZclFrameControl::MANUFACTURER_SPECIFIC)
+        {
+            true => {
+                let code = u16::read(stream, max_size)?;
+                max_size -= code.byte_size();
+                Some(code)
+            }
+            false => None,
+        };
+        let transaction_sequence = u8::read(stream, 1)?;
+        let command_id = u8::read(stream, 1)?;
+        let payload = stream::HungryVec::read(stream, max_size)?;
+        Ok(Self {
+            frame_control,
+            manufacturer_code,
+            transaction_sequence,
+            command_id,
+            payload,
+        })
+    }
+    fn write<T: WriteStream>(&self, stream: &mut T) {
+        self.frame_control.write(stream);
+        if let Some(code) = self.manufacturer_code {
+            code.write(stream);
+        }
+        self.transaction_sequence.write(stream);
+        self.command_id.write(stream);
+        self.payload.write(stream);
+    }
+}
+
 impl PowerSource {
     #[inline]
     pub fn battery_backup(&self) -> bool {
@@ -164,12 +234,6 @@ impl PowerSource {
     }
 }
 
-impl Default for PhysicalEnvironment {
-    fn default() -> Self {
-        Self::UnknownEnvironment
-    }
-}
-
 bitflags! {
     #[derive(Default, InnerData)]
     pub struct AlarmMask: u8 {